@@ -0,0 +1,489 @@
+use bevy::{prelude::*, ui::FocusPolicy, render::render_resource::TextureFormat};
+use serde::{Serialize, Deserialize};
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::{UiAssets, UiButton, OPTION_COLOR, SELECTED_OPTION_COLOR, despawn_screen, show_buttons},
+	settings::Settings,
+	art::SpriteSheets,
+	enums::MoleculeType,
+	save::MOLECULE_TYPES,
+	locale::Locale,
+};
+
+// ---------- PLUGINS ----------
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.insert_resource(AccessibilityMenu {
+			dirty: false,
+		})
+		.add_system(add_symbols)
+		.add_system(recolor_molecule_sheets)
+		// ACCESSIBILITY MENU
+		.add_system_set(SystemSet::on_enter(GameState::SettingsAccessibilityMenu)
+			.with_system(open_accessibility_menu)
+		)
+		.add_system_set(SystemSet::on_update(GameState::SettingsAccessibilityMenu)
+			.with_system(show_buttons)
+			.with_system(button_accessibility_option)
+			.with_system(button_accessibility_back)
+			.with_system(load_accessibility_menu.after(button_accessibility_option))
+		)
+		.add_system_set(SystemSet::on_exit(GameState::SettingsAccessibilityMenu)
+			.with_system(despawn_screen::<AccessibilityMenuUi>)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+const PALETTES: [Palette; 4] = [
+	Palette::Standard,
+	Palette::RedGreen,
+	Palette::BlueYellow,
+	Palette::Monochrome,
+];
+
+// Symbol height as a fraction of the sprite it sits on
+const SYMBOL_SCALE: f32 = 0.6;
+
+// Molecule sheets are 4 frames of 32 pixels across, the padlock frames after the unlocked ones keep their colours
+const SHEET_COLUMNS: usize = 4;
+const FRAME_SIZE: usize = 32;
+const UNLOCKED_FRAMES: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+	pub palette: Palette,
+	// Draws each molecule's symbol over its sprites and names molecules after them
+	pub symbols: bool,
+}
+
+// Molecule colours picked to stay apart for each kind of colour blindness
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum Palette {
+	#[default]
+	Standard,
+	// Protanopia and deuteranopia
+	RedGreen,
+	// Tritanopia
+	BlueYellow,
+	// Only brightness differs
+	Monochrome,
+}
+
+impl Palette {
+	fn name<'a>(&self, locale: &'a Locale) -> &'a str {
+		locale.get(match self {
+			Palette::Standard => "palette.standard",
+			Palette::RedGreen => "palette.red_green",
+			Palette::BlueYellow => "palette.blue_yellow",
+			Palette::Monochrome => "palette.monochrome",
+		})
+	}
+
+	// Flat colour for UI elements tied to a molecule type
+	pub fn color(&self, molecule_type: MoleculeType) -> Color {
+		match (self, molecule_type) {
+			(Palette::Standard, _) => molecule_type.color(),
+			(Palette::RedGreen, MoleculeType::Red) => Color::rgb(0.84, 0.37, 0.0),
+			(Palette::RedGreen, MoleculeType::Blue) => Color::rgb(0.0, 0.45, 0.7),
+			(Palette::RedGreen, MoleculeType::Orange) => Color::rgb(0.94, 0.89, 0.26),
+			(Palette::RedGreen, MoleculeType::Purple) => Color::rgb(0.8, 0.47, 0.65),
+			(Palette::RedGreen, MoleculeType::Gray) => Color::rgb(0.2, 0.2, 0.2),
+			(Palette::BlueYellow, MoleculeType::Red) => Color::rgb(0.65, 0.05, 0.1),
+			(Palette::BlueYellow, MoleculeType::Blue) => Color::rgb(0.0, 0.55, 0.55),
+			(Palette::BlueYellow, MoleculeType::Orange) => Color::rgb(1.0, 0.55, 0.7),
+			(Palette::BlueYellow, MoleculeType::Purple) => Color::rgb(0.25, 0.15, 0.3),
+			(Palette::BlueYellow, MoleculeType::Gray) => Color::rgb(0.85, 0.85, 0.85),
+			(Palette::Monochrome, MoleculeType::Red) => Color::rgb(0.75, 0.75, 0.75),
+			(Palette::Monochrome, MoleculeType::Blue) => Color::rgb(0.35, 0.35, 0.35),
+			(Palette::Monochrome, MoleculeType::Orange) => Color::rgb(0.95, 0.95, 0.95),
+			(Palette::Monochrome, MoleculeType::Purple) => Color::rgb(0.15, 0.15, 0.15),
+			(Palette::Monochrome, MoleculeType::Gray) => Color::rgb(0.55, 0.55, 0.55),
+		}
+	}
+
+	// Repaints the unlocked frames of a molecule sheet in this palette's colour, keeping the art's brightness
+	fn recolor(&self, molecule_type: MoleculeType, art: &Image) -> Image {
+		let mut image = art.clone();
+		if image.texture_descriptor.format != TextureFormat::Rgba8UnormSrgb {
+			warn!("Can't recolour {molecule_type:?} molecule art stored as {:?}", image.texture_descriptor.format);
+			return image;
+		}
+
+		let width = image.texture_descriptor.size.width as usize;
+		let target = self.color(molecule_type);
+		let base = luminance(molecule_type.color());
+		for (index, pixel) in image.data.chunks_exact_mut(4).enumerate() {
+			let (x, y) = (index % width, index / width);
+			if (y / FRAME_SIZE) * SHEET_COLUMNS + x / FRAME_SIZE >= UNLOCKED_FRAMES {
+				continue;
+			}
+			let shade = luminance(Color::rgb_u8(pixel[0], pixel[1], pixel[2])) / base;
+			pixel[0] = (target.r() * shade * 255.0).min(255.0) as u8;
+			pixel[1] = (target.g() * shade * 255.0).min(255.0) as u8;
+			pixel[2] = (target.b() * shade * 255.0).min(255.0) as u8;
+		}
+		image
+	}
+}
+
+fn luminance(color: Color) -> f32 {
+	0.2126 * color.r() + 0.7152 * color.g() + 0.0722 * color.b()
+}
+
+// Set while the accessibility menu needs rebuilding
+struct AccessibilityMenu {
+	dirty: bool,
+}
+
+// ---------- COMPONENTS ----------
+// Sprite that shows its molecule's symbol while symbols are on
+#[derive(Component)]
+pub struct MoleculeSymbol {
+	pub molecule_type: MoleculeType,
+	// Sprite size in world units
+	pub size: f32,
+}
+
+#[derive(Component)]
+struct AccessibilityMenuUi;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+enum AccessibilityOption {
+	Palette(Palette),
+	Symbols(bool),
+}
+
+#[derive(Component)]
+struct AccessibilityBackButton;
+
+impl AccessibilityOption {
+	fn label(&self, locale: &Locale) -> String {
+		match self {
+			AccessibilityOption::Palette(palette) => palette.name(locale).to_string(),
+			AccessibilityOption::Symbols(true) => locale.get("display.on").to_string(),
+			AccessibilityOption::Symbols(false) => locale.get("display.off").to_string(),
+		}
+	}
+
+	fn is_selected(&self, accessibility: &AccessibilitySettings) -> bool {
+		match *self {
+			AccessibilityOption::Palette(palette) => accessibility.palette == palette,
+			AccessibilityOption::Symbols(symbols) => accessibility.symbols == symbols,
+		}
+	}
+
+	fn apply(&self, accessibility: &mut AccessibilitySettings) {
+		match *self {
+			AccessibilityOption::Palette(palette) => accessibility.palette = palette,
+			AccessibilityOption::Symbols(symbols) => accessibility.symbols = symbols,
+		}
+	}
+}
+
+// ---------- SYSTEMS ----------
+// Settings can only change from the start menu, so sprites only need their symbol when they spawn
+fn add_symbols (
+	mut commands: Commands,
+	settings: Res<Settings>,
+	ui_assets: Res<UiAssets>,
+	symbol_query: Query<(Entity, &MoleculeSymbol), Added<MoleculeSymbol>>,
+) {
+	if !settings.accessibility.symbols {
+		return;
+	}
+	for (entity, symbol) in symbol_query.iter() {
+		let font_size = symbol.size * SYMBOL_SCALE;
+		let text_style = TextStyle {
+			font: ui_assets.font.clone(),
+			font_size,
+			color: Color::WHITE,
+		};
+		commands.entity(entity).with_children(|parent| {
+			// Dark shadow keeps it readable on light and dark molecules alike
+			parent.spawn_bundle(Text2dBundle {
+				text: Text::from_section(
+					symbol.molecule_type.symbol(),
+					TextStyle {
+						color: Color::rgba(0.0, 0.0, 0.0, 0.7),
+						..text_style.clone()
+					},
+				).with_alignment(TextAlignment::CENTER),
+				transform: Transform::from_xyz(font_size / 12.0, -font_size / 12.0, 0.04),
+				..default()
+			});
+			parent.spawn_bundle(Text2dBundle {
+				text: Text::from_section(
+					symbol.molecule_type.symbol(),
+					text_style,
+				).with_alignment(TextAlignment::CENTER),
+				transform: Transform::from_xyz(0.0, 0.0, 0.05),
+				..default()
+			});
+		});
+	}
+}
+
+// Points the molecule atlases at copies of the art in the chosen palette
+fn recolor_molecule_sheets (
+	settings: Res<Settings>,
+	spritesheets: Res<SpriteSheets>,
+	mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+	mut images: ResMut<Assets<Image>>,
+	mut art: Local<Vec<Handle<Image>>>,
+	mut applied: Local<Option<Palette>>,
+) {
+	let palette = settings.accessibility.palette;
+	if *applied == Some(palette) {
+		return;
+	}
+
+	// Hold on to the original art, the atlases only keep the copy in use
+	if art.is_empty() {
+		*art = MOLECULE_TYPES.iter()
+			.filter_map(|molecule_type| texture_atlases.get(&spritesheets.get_molecule_atlas(*molecule_type)))
+			.map(|atlas| atlas.texture.clone())
+			.collect();
+	}
+	if art.iter().any(|handle| images.get(handle).is_none()) {
+		return;
+	}
+
+	for (molecule_type, handle) in MOLECULE_TYPES.iter().zip(art.iter()) {
+		let texture = if palette == Palette::Standard {
+			handle.clone()
+		} else {
+			let recolored = palette.recolor(*molecule_type, images.get(handle).unwrap());
+			images.add(recolored)
+		};
+		if let Some(atlas) = texture_atlases.get_mut(&spritesheets.get_molecule_atlas(*molecule_type)) {
+			atlas.texture = texture;
+		}
+	}
+	*applied = Some(palette);
+}
+
+fn open_accessibility_menu (
+	mut accessibility_menu: ResMut<AccessibilityMenu>,
+) {
+	accessibility_menu.dirty = true;
+}
+
+// Rebuilt whenever an option is picked so the selection and preview follow
+fn load_accessibility_menu (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	mut accessibility_menu: ResMut<AccessibilityMenu>,
+	ui_query: Query<Entity, With<AccessibilityMenuUi>>,
+) {
+	if !accessibility_menu.dirty {
+		return;
+	}
+	accessibility_menu.dirty = false;
+	for entity in ui_query.iter() {
+		commands.entity(entity).despawn_recursive();
+	}
+
+	let accessibility = &settings.accessibility;
+	let text_style = TextStyle {
+		font: ui_assets.font.clone(),
+		font_size: 28.0,
+		color: ui_assets.text_color,
+	};
+
+	let rows = [
+		("accessibility.palette", PALETTES.iter().map(|palette| AccessibilityOption::Palette(*palette)).collect::<Vec<_>>()),
+		("accessibility.symbols", vec![AccessibilityOption::Symbols(true), AccessibilityOption::Symbols(false)]),
+	];
+
+	let row_style = Style {
+		size: Size::new(Val::Px(1000.0), Val::Px(54.0)),
+		justify_content: JustifyContent::SpaceBetween,
+		align_items: AlignItems::Center,
+		..default()
+	};
+
+	commands
+		// Node Bundle to hold the Accessibility Menu UI elements
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				margin: UiRect::all(Val::Auto),
+				flex_direction: FlexDirection::ColumnReverse,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(AccessibilityMenuUi)
+		.insert(Name::new("Accessibility Menu UI"))
+		.with_children(|parent| {
+
+			// Option Rows
+			for (title, options) in rows {
+				parent
+					.spawn_bundle(NodeBundle {
+						style: row_style.clone(),
+						color: INVISIBLE.into(),
+						..default()
+					})
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							locale.get(title),
+							text_style.clone(),
+						));
+
+						parent
+							.spawn_bundle(NodeBundle {
+								color: INVISIBLE.into(),
+								..default()
+							})
+							.with_children(|parent| {
+								for option in options {
+									// Option Button, highlighted while it's the current choice
+									parent
+										.spawn_bundle(ButtonBundle {
+											style: Style {
+												size: Size::new(Val::Px(190.0), Val::Px(44.0)),
+												margin: UiRect::all(Val::Px(5.0)),
+												justify_content: JustifyContent::Center,
+												align_items: AlignItems::Center,
+												..default()
+											},
+											color: if option.is_selected(accessibility) {SELECTED_OPTION_COLOR.into()} else {OPTION_COLOR.into()},
+											..default()
+										})
+										.insert(option)
+										.with_children(|parent| {
+											parent.spawn_bundle(TextBundle::from_section(
+												option.label(&locale),
+												text_style.clone(),
+											));
+										});
+								}
+							});
+					});
+			}
+
+			// Preview Row, a swatch per molecule in the chosen palette
+			parent
+				.spawn_bundle(NodeBundle {
+					style: row_style.clone(),
+					color: INVISIBLE.into(),
+					..default()
+				})
+				.with_children(|parent| {
+					parent.spawn_bundle(TextBundle::from_section(
+						locale.get("accessibility.preview"),
+						text_style.clone(),
+					));
+
+					parent
+						.spawn_bundle(NodeBundle {
+							color: INVISIBLE.into(),
+							..default()
+						})
+						.with_children(|parent| {
+							for molecule_type in MOLECULE_TYPES {
+								parent
+									.spawn_bundle(NodeBundle {
+										style: Style {
+											size: Size::new(Val::Px(44.0), Val::Px(44.0)),
+											margin: UiRect::all(Val::Px(5.0)),
+											justify_content: JustifyContent::Center,
+											align_items: AlignItems::Center,
+											..default()
+										},
+										color: accessibility.palette.color(molecule_type).into(),
+										..default()
+									})
+									.with_children(|parent| {
+										if accessibility.symbols {
+											parent.spawn_bundle(TextBundle::from_section(
+												molecule_type.symbol(),
+												TextStyle {
+													color: Color::WHITE,
+													..text_style.clone()
+												},
+											));
+										}
+									});
+							}
+						});
+				});
+
+			// Back Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: Style {
+						size: Size::new(Val::Px(250.0), Val::Px(65.0)),
+						margin: UiRect::all(Val::Px(20.0)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					color: ui_assets.button_color.into(),
+					..default()
+				})
+				.insert(AccessibilityBackButton)
+				.insert(UiButton)
+				.with_children(|parent| {
+
+					// Back Button Sprite
+					parent
+						.spawn_bundle(ImageBundle {
+							style: Style {
+								size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+								justify_content: JustifyContent::Center,
+								align_items: AlignItems::Center,
+								..Default::default()
+							},
+						image: ui_assets.button_idle.clone().into(),
+						..Default::default()
+					})
+					.insert(FocusPolicy::Pass)
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							locale.get("menu.back"),
+							TextStyle {
+								font: ui_assets.font.clone(),
+								font_size: 40.0,
+								color: ui_assets.text_color,
+							},
+						));
+					});
+				});
+		});
+}
+
+fn button_accessibility_option (
+	interaction_query: Query<(&AccessibilityOption, &Interaction), Changed<Interaction>>,
+	mut settings: ResMut<Settings>,
+	mut accessibility_menu: ResMut<AccessibilityMenu>,
+) {
+	for (option, interaction) in interaction_query.iter() {
+		if *interaction == Interaction::Clicked && !option.is_selected(&settings.accessibility) {
+			option.apply(&mut settings.accessibility);
+			accessibility_menu.dirty = true;
+		}
+	}
+}
+
+fn button_accessibility_back (
+	interaction_query: Query<(&AccessibilityBackButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, interaction) in interaction_query.iter() {
+		if *interaction == Interaction::Clicked {
+			state.pop().expect("Failed to change states");
+		}
+	}
+}
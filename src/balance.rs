@@ -0,0 +1,123 @@
+use bevy::{
+	prelude::*,
+	asset::{AssetLoader, LoadContext, LoadedAsset},
+	reflect::TypeUuid,
+	utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+// ---------- PLUGINS ----------
+pub struct BalancePlugin;
+
+impl Plugin for BalancePlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_asset::<BalanceConfig>()
+		.init_asset_loader::<BalanceLoader>()
+		// Used until the file has loaded, or if it fails to
+		.insert_resource(BalanceConfig::default())
+		.add_startup_system(load_balance)
+		.add_system(apply_balance)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+// Watched for changes in debug builds, see main
+const BALANCE_FILE: &str = "game.balance.ron";
+
+// Tuning values, keys missing from the file keep these defaults
+#[derive(Deserialize, TypeUuid, Clone, Debug)]
+#[uuid = "6f1c2a0e-93b4-4d5e-8a27-3c4f0d9b71e2"]
+#[serde(default)]
+pub struct BalanceConfig {
+	// Game length in seconds
+	pub day_length: f32,
+	pub start_power: f32,
+	// Target of the first campaign day, later days scale this up
+	pub target_power: f32,
+	// Max pressure of steel walls, other materials scale this by their tolerance
+	pub max_pressure: f32,
+	// Controls the averaging of the pressure [0-1]
+	// too high values will lead to the pressure bouncing around unpredictably and killing the player instantly
+	// too low values will make the thermometer lag behind whatever the real value
+	pub pressure_sensitivity: f32,
+	// Demand multiplier applied to a molecule's price each time one is bought
+	pub cost_scaling: f32,
+	// Seconds per animation frame
+	pub animation_speed: f32,
+	// Top speed of a freshly spawned molecule of mass 1
+	pub velocity_factor: f32,
+	// Distance between centres at which molecules collide
+	pub collision_radius: f32,
+	// Battery discharge rate as a multiple of the average power a day's target needs
+	pub battery_headroom: f32,
+}
+
+impl Default for BalanceConfig {
+	fn default() -> Self {
+		Self {
+			day_length: 180.0,
+			start_power: 100.0,
+			target_power: 2000.0,
+			max_pressure: 50.0,
+			pressure_sensitivity: 0.05,
+			cost_scaling: 1.1,
+			animation_speed: 0.125,
+			velocity_factor: 4.0,
+			collision_radius: 32.0,
+			battery_headroom: 2.0,
+		}
+	}
+}
+
+// Keeps the file loaded so edits keep coming through
+struct BalanceHandle(Handle<BalanceConfig>);
+
+#[derive(Default)]
+struct BalanceLoader;
+
+impl AssetLoader for BalanceLoader {
+	fn load<'a>(
+		&'a self,
+		bytes: &'a [u8],
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+		Box::pin(async move {
+			let config = ron::de::from_bytes::<BalanceConfig>(bytes)?;
+			load_context.set_default_asset(LoadedAsset::new(config));
+			Ok(())
+		})
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["balance.ron"]
+	}
+}
+
+// ---------- SYSTEMS ----------
+fn load_balance (
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+) {
+	commands.insert_resource(BalanceHandle(asset_server.load(BALANCE_FILE)));
+}
+
+// Copies the file into the resource whenever it loads or changes on disk
+fn apply_balance (
+	mut events: EventReader<AssetEvent<BalanceConfig>>,
+	configs: Res<Assets<BalanceConfig>>,
+	handle: Res<BalanceHandle>,
+	mut balance: ResMut<BalanceConfig>,
+) {
+	for event in events.iter() {
+		match event {
+			AssetEvent::Created {handle: changed} | AssetEvent::Modified {handle: changed} if *changed == handle.0 => {
+				if let Some(config) = configs.get(&handle.0) {
+					*balance = config.clone();
+				}
+			}
+			_ => {}
+		}
+	}
+}
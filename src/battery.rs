@@ -0,0 +1,192 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::Inspectable;
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::UiAssets,
+	reactor::GodMode,
+	reactor_ui::ReactorUi,
+	economy::{Economy, Power, Temperature, CashFlow, INCOME_SMOOTHING},
+	grid::GridLabel,
+	shop::Upgrades,
+	settings::Settings,
+	controls::{GameSpeed, Actions, InputAction},
+	layout::WorldAnchor,
+	campaign::Campaign,
+	balance::BalanceConfig,
+	locale::Locale,
+};
+
+// ---------- PLUGINS ----------
+pub struct BatteryPlugin;
+
+impl Plugin for BatteryPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_system_set(SystemSet::on_enter(GameState::Reactor)
+			.with_system(generate_battery)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(update_battery.label(BatteryLabel).before(GridLabel))
+			.with_system(toggle_discharge)
+			.with_system(update_battery_text)
+		)
+		;
+	}
+}
+
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BatteryLabel;
+
+// ---------- RESOURCES ----------
+const BATTERY_CAPACITY: f32 = 600.0;
+// Power per second the battery can take in from reactions
+const CHARGE_RATE: f32 = 60.0;
+// Power per second the battery can feed to the grid, at least
+const DISCHARGE_RATE: f32 = 25.0;
+// Fraction of the stored charge lost per second
+const SELF_DISCHARGE: f32 = 0.01;
+// Pressure added per unit of power the battery couldn't take
+const SPILL_HEAT: f32 = 0.05;
+
+// ---------- COMPONENTS ----------
+#[derive(Component, Inspectable)]
+pub struct Battery {
+	pub stored: f32,
+	pub capacity: f32,
+	pub charge_rate: f32,
+	pub discharge_rate: f32,
+	pub self_discharge: f32,
+	// Reaction energy waiting to be charged, at most a second's worth
+	pub incoming: f32,
+	// Feeding the grid, otherwise holding charge for later
+	pub discharging: bool,
+}
+
+#[derive(Component)]
+struct BatteryText;
+
+// ---------- SYSTEMS ----------
+fn generate_battery (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	upgrades_query: Query<&Upgrades>,
+	campaign_query: Query<&Campaign>,
+	settings: Res<Settings>,
+	balance: Res<BalanceConfig>,
+) {
+	let upgrades = upgrades_query.single();
+	let campaign = campaign_query.single();
+
+	// Every reaction goes through the battery, so later days' targets need faster rates
+	let target_power = balance.target_power * campaign.plan().target_scale;
+	let day_length = balance.day_length * campaign.day_length_scale();
+	let rate_scale = (target_power / day_length * balance.battery_headroom / DISCHARGE_RATE).max(1.0);
+
+	commands
+		.spawn()
+		.insert(Battery {
+			stored: 0.0,
+			capacity: BATTERY_CAPACITY * upgrades.battery_scale(),
+			charge_rate: CHARGE_RATE * rate_scale,
+			discharge_rate: DISCHARGE_RATE * rate_scale,
+			self_discharge: SELF_DISCHARGE,
+			incoming: 0.0,
+			discharging: settings.gameplay.battery_discharging,
+		})
+		.insert(Economy)
+		.insert(Name::new("Battery"));
+
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(615.0, -310.0), Vec2::new(300.0, 60.0)))
+		.insert(Name::new("Battery Node"))
+		.insert(ReactorUi)
+		.with_children(|parent| {
+			parent
+				.spawn_bundle(TextBundle::from_section(
+					"",
+					TextStyle {
+						font: ui_assets.font.clone(),
+						font_size: 18.0,
+						color: Color::rgb(0.0, 0.3, 0.0),
+					},
+				))
+				.insert(BatteryText);
+		});
+}
+
+// Charges from reactions, leaks, and feeds the grid
+fn update_battery (
+	time: Res<Time>,
+	speed: Res<GameSpeed>,
+	mut battery_query: Query<&mut Battery>,
+	mut economy_query: Query<(&Economy, &mut Power, &mut Temperature, &mut CashFlow)>,
+	godmode_query: Query<&GodMode>,
+) {
+	let mut battery = battery_query.single_mut();
+	let (_economy, mut power, mut pressure, mut cash_flow) = economy_query.single_mut();
+	let godmode = godmode_query.single();
+	let delta = speed.delta_seconds(&time);
+
+	let charge = battery.incoming
+		.min(battery.charge_rate * delta)
+		.min(battery.capacity - battery.stored)
+		.max(0.0);
+	battery.stored += charge;
+	battery.incoming -= charge;
+
+	// Whatever can't wait for the next second turns into heat
+	let spilled = (battery.incoming - battery.charge_rate).max(0.0);
+	if spilled > 0.0 {
+		battery.incoming -= spilled;
+		if !godmode.0 {
+			pressure.current_pressure += spilled * SPILL_HEAT;
+		}
+	}
+
+	battery.stored *= (-battery.self_discharge * delta).exp();
+
+	let discharge = if battery.discharging {battery.stored.min(battery.discharge_rate * delta)} else {0.0};
+	battery.stored -= discharge;
+	power.current_power = (power.current_power + discharge).clamp(0.0, 999000.0);
+	power.generated += discharge;
+
+	if delta > 0.0 {
+		cash_flow.income += (discharge / delta - cash_flow.income) * (delta / INCOME_SMOOTHING).min(1.0);
+	}
+}
+
+fn toggle_discharge (
+	actions: Actions,
+	mut battery_query: Query<&mut Battery>,
+) {
+	if actions.just_pressed(InputAction::ToggleDischarge) {
+		let mut battery = battery_query.single_mut();
+		battery.discharging = !battery.discharging;
+	}
+}
+
+fn update_battery_text (
+	battery_query: Query<&Battery>,
+	mut text_query: Query<&mut Text, With<BatteryText>>,
+	locale: Res<Locale>,
+	settings: Res<Settings>,
+) {
+	let battery = battery_query.single();
+	let mut text = text_query.single_mut();
+
+	text.sections[0].value = locale.format("battery.status", &[
+		("stored", &format!("{:.0}", battery.stored)),
+		("capacity", &format!("{:.0}", battery.capacity)),
+		("mode", &locale.get(if battery.discharging {"battery.discharging"} else {"battery.holding"})),
+		("key", &settings.controls.binding(InputAction::ToggleDischarge).label()),
+	]);
+}
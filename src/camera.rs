@@ -1,6 +1,8 @@
-use bevy::{prelude::*, input::mouse::MouseMotion};
+use bevy::{prelude::*, input::mouse::MouseMotion, render::camera::ScalingMode};
 use bevy_inspector_egui::Inspectable;
 
+use crate::layout::WORLD_HEIGHT;
+
 // ---------- PLUGINS ----------
 pub struct CameraPlugin;
 
@@ -25,8 +27,11 @@ pub struct PanCamera {
 fn setup_camera(
 	mut commands: Commands
 ) {
+	let mut camera = Camera2dBundle::default();
+	// The world is laid out 900 units tall, whatever resolution the window has
+	camera.projection.scaling_mode = ScalingMode::FixedVertical(WORLD_HEIGHT);
 	commands
-	.spawn_bundle(Camera2dBundle::default())
+	.spawn_bundle(camera)
 	.insert(PanCamera {
 		focus: Vec2::new(0.0, 0.0)
 	});
@@ -56,3 +61,20 @@ fn pan_camera(
 		}
 	}
 }
+
+// Converts the cursor position into world coordinates through the camera
+pub fn cursor_world_position(
+	windows: &Windows,
+	camera: &Camera,
+	camera_transform: &GlobalTransform,
+) -> Option<Vec2> {
+	let window = windows.get_primary()?;
+	let cursor = window.cursor_position()?;
+	let window_size = Vec2::new(window.width(), window.height());
+
+	// Window coordinates to normalised device coordinates [-1, 1]
+	let ndc = (cursor / window_size) * 2.0 - Vec2::ONE;
+	let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix().inverse();
+
+	Some(ndc_to_world.project_point3(ndc.extend(-1.0)).truncate())
+}
@@ -0,0 +1,283 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+use serde::{Serialize, Deserialize};
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::{despawn_screen, UiAssets},
+	enums::MoleculeType,
+	locale::Locale,
+};
+
+// ---------- PLUGINS ----------
+pub struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_startup_system(generate_campaign)
+		.add_system_set(SystemSet::on_enter(GameState::DaySummary)
+			.with_system(load_day_summary)
+		)
+		.add_system_set(SystemSet::on_update(GameState::DaySummary)
+			.with_system(button_next_day)
+		)
+		.add_system_set(SystemSet::on_exit(GameState::DaySummary)
+			.with_system(despawn_screen::<DaySummaryUi>)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+// Restrictions placed on a single day
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DayConstraint {
+	// Molecule can't be bought this day
+	BannedMolecule(MoleculeType),
+	// Multiplier on the walls' max pressure
+	PressureLimit(f32),
+	// Multiplier on the day length
+	DayLength(f32),
+}
+
+impl DayConstraint {
+	pub fn describe(&self, locale: &Locale) -> String {
+		match self {
+			DayConstraint::BannedMolecule(molecule_type) => locale.format("constraint.banned", &[("molecule", &molecule_type.short_name(locale))]),
+			DayConstraint::PressureLimit(scale) => locale.format("constraint.pressure", &[("percent", &format!("{:.0}", scale * 100.0))]),
+			DayConstraint::DayLength(scale) => locale.format("constraint.day_length", &[("percent", &format!("{:.0}", scale * 100.0))]),
+		}
+	}
+}
+
+pub struct DayPlan {
+	// Multiplier on the balance target power
+	pub target_scale: f32,
+	pub constraints: &'static [DayConstraint],
+	// Molecules unlocked at the start of the day, carried into later days
+	pub unlocks: &'static [MoleculeType],
+}
+
+pub const CAMPAIGN: [DayPlan; 5] = [
+	DayPlan {
+		target_scale: 1.0,
+		constraints: &[],
+		unlocks: &[],
+	},
+	DayPlan {
+		target_scale: 1.5,
+		constraints: &[DayConstraint::PressureLimit(0.8)],
+		unlocks: &[MoleculeType::Gray],
+	},
+	DayPlan {
+		target_scale: 2.25,
+		constraints: &[DayConstraint::BannedMolecule(MoleculeType::Gray)],
+		unlocks: &[MoleculeType::Purple],
+	},
+	DayPlan {
+		target_scale: 3.25,
+		constraints: &[DayConstraint::DayLength(0.85)],
+		unlocks: &[],
+	},
+	DayPlan {
+		target_scale: 4.5,
+		constraints: &[DayConstraint::PressureLimit(0.7), DayConstraint::DayLength(0.85)],
+		unlocks: &[MoleculeType::Orange],
+	},
+];
+
+// ---------- COMPONENTS ----------
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DayResult {
+	pub day: usize,
+	pub power: f32,
+	pub target: f32,
+}
+
+// Progress through the campaign, kept between days
+#[derive(Component)]
+pub struct Campaign {
+	// Index into CAMPAIGN of the day being played
+	pub day: usize,
+	pub results: Vec<DayResult>,
+	// Set when the last day is won, cleared when the next day begins
+	pub finished: bool,
+}
+
+impl Campaign {
+	pub fn plan(&self) -> &'static DayPlan {
+		&CAMPAIGN[self.day.min(CAMPAIGN.len() - 1)]
+	}
+
+	pub fn is_final_day(&self) -> bool {
+		self.day + 1 >= CAMPAIGN.len()
+	}
+
+	pub fn is_banned(&self, molecule_type: MoleculeType) -> bool {
+		self.plan().constraints.contains(&DayConstraint::BannedMolecule(molecule_type))
+	}
+
+	pub fn pressure_scale(&self) -> f32 {
+		self.plan().constraints.iter().fold(1.0, |scale, constraint| match constraint {
+			DayConstraint::PressureLimit(limit) => scale * limit,
+			_ => scale,
+		})
+	}
+
+	pub fn day_length_scale(&self) -> f32 {
+		self.plan().constraints.iter().fold(1.0, |scale, constraint| match constraint {
+			DayConstraint::DayLength(length) => scale * length,
+			_ => scale,
+		})
+	}
+
+	// Records a won day and moves on, wrapping back to the first day after the last
+	pub fn complete_day(&mut self, power: f32, target: f32) {
+		self.results.push(DayResult {
+			day: self.day,
+			power,
+			target,
+		});
+		if self.is_final_day() {
+			self.day = 0;
+			self.finished = true;
+		} else {
+			self.day += 1;
+		}
+	}
+}
+
+#[derive(Component)]
+struct DaySummaryUi;
+
+#[derive(Component)]
+struct NextDayButton;
+
+// ---------- SYSTEMS ----------
+fn generate_campaign (
+	mut commands: Commands,
+) {
+	commands
+		.spawn()
+		.insert(Campaign {
+			day: 0,
+			results: Vec::new(),
+			finished: false,
+		})
+		.insert(Name::new("Campaign"));
+}
+
+fn load_day_summary (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
+	campaign_query: Query<&Campaign>,
+) {
+	let campaign = campaign_query.single();
+
+	let button_style = Style {
+		size: Size::new(Val::Px(250.0), Val::Px(65.0)),
+		margin: UiRect::all(Val::Px(20.0)),
+		justify_content: JustifyContent::Center,
+		align_items: AlignItems::Center,
+		..default()
+	};
+
+	let text_style = TextStyle {
+		font: ui_assets.font.clone(),
+		font_size: 40.0,
+		color: ui_assets.text_color,
+	};
+
+	// Summary of the day just finished and a preview of the next one
+	let mut lines = Vec::new();
+	if let Some(result) = campaign.results.last() {
+		lines.push(locale.format("summary.day_complete", &[("day", &(result.day + 1))]));
+		lines.push(locale.format("summary.power", &[("power", &format!("{:.0}", result.power)), ("target", &format!("{:.0}", result.target))]));
+	}
+	lines.push(locale.format("summary.next", &[("day", &(campaign.day + 1)), ("days", &CAMPAIGN.len())]));
+	for unlock in campaign.plan().unlocks {
+		lines.push(locale.format("summary.unlocks", &[("molecule", &unlock.name(&locale))]));
+	}
+	for constraint in campaign.plan().constraints {
+		lines.push(constraint.describe(&locale));
+	}
+
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				margin: UiRect::all(Val::Auto),
+				flex_direction: FlexDirection::ColumnReverse,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(DaySummaryUi)
+		.insert(Name::new("Day Summary UI"))
+		.with_children(|parent| {
+			for line in lines {
+				parent.spawn_bundle(TextBundle::from_section(
+					line,
+					text_style.clone(),
+				));
+			}
+
+			// Next Day Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: button_style.clone(),
+					color: ui_assets.button_color.into(),
+					..default()
+				})
+				.insert(NextDayButton)
+				.with_children(|parent| {
+
+					// Next Day Button Sprite
+					parent
+						.spawn_bundle(ImageBundle {
+							style: Style {
+								size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+								justify_content: JustifyContent::Center,
+								align_items: AlignItems::Center,
+								..Default::default()
+							},
+						image: ui_assets.button_idle.clone().into(),
+						..Default::default()
+					})
+					.insert(FocusPolicy::Pass)
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							locale.get("summary.back"),
+							text_style.clone(),
+						));
+					});
+				});
+		});
+}
+
+fn button_next_day (
+	mut interaction_query: Query<(&NextDayButton, &Children, &Interaction), Changed<Interaction>>,
+	mut image_query: Query<&mut UiImage>,
+	ui_assets: Res<UiAssets>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, children, interaction) in interaction_query.iter_mut() {
+		let child = children.iter().next().unwrap();
+		let mut image = image_query.get_mut(*child).unwrap();
+
+		match interaction {
+			Interaction::Clicked => {
+				// Win screen and summary replaced the reactor on the stack so this lands back in the Lab
+				state.pop().expect("Failed to change states");
+			}
+			Interaction::Hovered => {
+				image.0 = ui_assets.button_hovered.clone();
+			}
+			Interaction::None => {
+				image.0 = ui_assets.button_idle.clone();
+			}
+		}
+	}
+}
@@ -0,0 +1,323 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::Inspectable;
+use serde::{Serialize, Deserialize};
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::{despawn_screen, UiAssets},
+	reactor::{Molecule, Dimensions},
+	reactor_ui::{ReactorUi, REACTOR_CENTER, REACTOR_SIZE},
+	shop::Upgrades,
+	economy::{Economy, Power, Market},
+	enums::MoleculeType,
+	sales::{Ledger, SaleSource, sell_molecule},
+	research::{Research, ResearchEffect, ReactorPart},
+	layout::WorldAnchor,
+	locale::Locale,
+	settings::Settings,
+};
+
+// ---------- PLUGINS ----------
+pub struct CollectorPlugin;
+
+impl Plugin for CollectorPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_startup_system(generate_inventory)
+		.add_system_set(SystemSet::on_enter(GameState::Reactor)
+			.with_system(generate_collectors)
+		)
+		.add_system_set(SystemSet::on_exit(GameState::Reactor)
+			.with_system(despawn_screen::<Collector>)
+		)
+		.add_system_set(SystemSet::on_pause(GameState::Reactor)
+			.with_system(despawn_screen::<Collector>)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(collect_molecules)
+			.with_system(button_collector)
+			.with_system(update_collector_text)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+// Ports sit in the middle of a reactor wall, given by the direction of the wall and the port size
+const COLLECTOR_PORTS: [(Vec2, Vec2); 3] = [
+	// Top wall
+	(Vec2::Y, Vec2::new(96.0, 24.0)),
+	// Right wall
+	(Vec2::X, Vec2::new(24.0, 96.0)),
+	// Bottom wall
+	(Vec2::NEG_Y, Vec2::new(96.0, 24.0)),
+];
+
+// ---------- COMPONENTS ----------
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CollectorMode {
+	// Moves harvested molecules into the inventory
+	Store,
+	// Credits power for harvested molecules
+	Sell,
+}
+
+#[derive(Component)]
+pub struct Collector {
+	pub accepts: Option<MoleculeType>,
+	pub mode: CollectorMode,
+}
+
+impl Collector {
+	// Cycles Off -> Red Store -> Red Sell -> Blue Store -> ... -> Gray Sell -> Off
+	pub fn cycle(&mut self) {
+		let order = [
+			MoleculeType::Red,
+			MoleculeType::Blue,
+			MoleculeType::Orange,
+			MoleculeType::Purple,
+			MoleculeType::Gray,
+		];
+		match (self.accepts, self.mode) {
+			(None, _) => {
+				self.accepts = Some(order[0]);
+				self.mode = CollectorMode::Store;
+			}
+			(Some(_), CollectorMode::Store) => {
+				self.mode = CollectorMode::Sell;
+			}
+			(Some(molecule_type), CollectorMode::Sell) => {
+				let index = order.iter().position(|x| *x == molecule_type).unwrap();
+				self.accepts = order.get(index + 1).copied();
+				self.mode = CollectorMode::Store;
+			}
+		}
+	}
+
+	// Store ports also show how many of their molecule are in the inventory
+	pub fn label(&self, inventory: &Inventory, locale: &Locale) -> String {
+		match (self.accepts, self.mode) {
+			(None, _) => locale.get("collector.off").to_string(),
+			(Some(molecule_type), CollectorMode::Store) => locale.format("collector.store", &[("molecule", &molecule_type.short_name(locale)), ("count", &inventory.get_count(molecule_type))]),
+			(Some(molecule_type), CollectorMode::Sell) => locale.format("collector.sell", &[("molecule", &molecule_type.short_name(locale))]),
+		}
+	}
+}
+
+#[derive(Component, Inspectable, Serialize, Deserialize, Clone)]
+pub struct Inventory {
+	pub red_count: u32,
+	pub blue_count: u32,
+	pub orange_count: u32,
+	pub purple_count: u32,
+	pub gray_count: u32,
+}
+
+impl Inventory {
+	pub fn get_count(&self, molecule_type: MoleculeType) -> &u32 {
+		match molecule_type {
+			MoleculeType::Red => &self.red_count,
+			MoleculeType::Blue => &self.blue_count,
+			MoleculeType::Orange => &self.orange_count,
+			MoleculeType::Purple => &self.purple_count,
+			MoleculeType::Gray => &self.gray_count,
+		}
+	}
+
+	pub fn get_count_mut(&mut self, molecule_type: MoleculeType) -> &mut u32 {
+		match molecule_type {
+			MoleculeType::Red => &mut self.red_count,
+			MoleculeType::Blue => &mut self.blue_count,
+			MoleculeType::Orange => &mut self.orange_count,
+			MoleculeType::Purple => &mut self.purple_count,
+			MoleculeType::Gray => &mut self.gray_count,
+		}
+	}
+}
+
+// Links a port's button to the collector sprite it configures
+#[derive(Component)]
+struct CollectorButton(Entity);
+
+#[derive(Component)]
+struct CollectorText(Entity);
+
+// ---------- SYSTEMS ----------
+fn generate_inventory (
+	mut commands: Commands,
+) {
+	commands
+	.spawn()
+	.insert(Inventory {
+		red_count: 0,
+		blue_count: 0,
+		orange_count: 0,
+		purple_count: 0,
+		gray_count: 0,
+	})
+	.insert(Name::new("Inventory"));
+}
+
+fn generate_collectors (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
+	upgrades_query: Query<&Upgrades>,
+) {
+	// Follow the walls out when the vessel has been enlarged
+	let half_reactor = REACTOR_SIZE * upgrades_query.single().size_scale() / 2.0;
+
+	for (index, (wall, size)) in COLLECTOR_PORTS.iter().enumerate() {
+		let location = REACTOR_CENTER + *wall * half_reactor;
+		let collector = commands
+			.spawn_bundle(SpriteBundle {
+				sprite: Sprite {
+					color: Color::rgb(0.3, 0.3, 0.3),
+					custom_size: Some(*size),
+					..default()
+				},
+				transform: Transform::from_translation(location.extend(950.0)),
+				..default()
+			})
+			.insert(Name::new(format!("Collector {index}")))
+			.insert(Collector {
+				accepts: None,
+				mode: CollectorMode::Store,
+			})
+			// Not tagged Reactor as that would break the single reactor queries
+			.insert(Dimensions {
+				x_size: size.x,
+				y_size: size.y,
+			})
+			.id();
+
+		// Button is wider than the port so the label fits on the vertical ports
+		let button_size = Vec2::new(size.x.max(96.0), size.y.max(24.0));
+		commands
+			.spawn_bundle(NodeBundle {
+				style: Style {
+					position_type: PositionType::Absolute,
+					..default()
+				},
+				color: INVISIBLE.into(),
+				..default()
+			})
+			.insert(WorldAnchor::new(location, button_size))
+			.insert(Name::new(format!("Collector {index} Node")))
+			.insert(ReactorUi)
+			.with_children(|parent| {
+
+				// Collector Button
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: Style {
+							size: Size::new(Val::Px(button_size.x), Val::Px(button_size.y)),
+							justify_content: JustifyContent::Center,
+							align_items: AlignItems::Center,
+							..default()
+						},
+						color: INVISIBLE.into(),
+						..default()
+					})
+					.insert(CollectorButton(collector))
+					.with_children(|parent| {
+
+						// Collector Text
+						parent
+							.spawn_bundle(TextBundle::from_section(
+								locale.get("collector.off"),
+								TextStyle {
+									font: ui_assets.font.clone(),
+									font_size: 16.0,
+									color: Color::WHITE,
+								},
+							))
+							.insert(CollectorText(collector));
+					});
+			});
+	}
+}
+
+fn button_collector (
+	interaction_query: Query<(&CollectorButton, &Interaction), Changed<Interaction>>,
+	mut collector_query: Query<&mut Collector>,
+	research_query: Query<&Research>,
+) {
+	// Ports stay closed until they've been researched
+	if !research_query.single().is_unlocked(ResearchEffect::Part(ReactorPart::Collector)) {
+		return;
+	}
+
+	for (button, interaction) in interaction_query.iter() {
+		if *interaction == Interaction::Clicked {
+			if let Ok(mut collector) = collector_query.get_mut(button.0) {
+				collector.cycle();
+			}
+		}
+	}
+}
+
+fn update_collector_text (
+	collector_query: Query<&Collector>,
+	inventory_query: Query<&Inventory>,
+	locale: Res<Locale>,
+	mut text_query: Query<(&CollectorText, &mut Text)>,
+) {
+	let inventory = inventory_query.single();
+
+	for (collector_text, mut text) in text_query.iter_mut() {
+		if let Ok(collector) = collector_query.get(collector_text.0) {
+			text.sections[0].value = collector.label(inventory, &locale);
+		}
+	}
+}
+
+fn collect_molecules (
+	mut commands: Commands,
+	mut molecule_query: Query<(Entity, &mut Molecule, &Dimensions, &Transform), Without<Collector>>,
+	mut collector_query: Query<(&Collector, &Dimensions, &Transform, &mut Sprite)>,
+	mut inventory_query: Query<&mut Inventory>,
+	mut economy_query: Query<(&Economy, &mut Power)>,
+	market_query: Query<&Market>,
+	mut ledger_query: Query<&mut Ledger>,
+	settings: Res<Settings>,
+) {
+	let mut inventory = inventory_query.single_mut();
+	let (_economy, mut power) = economy_query.single_mut();
+	let market = market_query.single();
+	let mut ledger = ledger_query.single_mut();
+
+	for (collector, collector_size, collector_transform, mut sprite) in collector_query.iter_mut() {
+		// Tint the port with the colour of the molecule it harvests, its label names it too
+		sprite.color = match collector.accepts {
+			Some(molecule_type) => settings.accessibility.palette.color(molecule_type),
+			None => Color::rgb(0.3, 0.3, 0.3),
+		};
+
+		let accepts = match collector.accepts {
+			Some(molecule_type) => molecule_type,
+			None => continue,
+		};
+
+		for (entity, mut molecule, molecule_size, molecule_transform) in molecule_query.iter_mut() {
+			if molecule.reacted || molecule.variant != accepts {
+				continue;
+			}
+			let offset = (molecule_transform.translation - collector_transform.translation).abs();
+			if offset.x <= (collector_size.x_size + molecule_size.x_size) / 2.0
+			&& offset.y <= (collector_size.y_size + molecule_size.y_size) / 2.0 {
+				match collector.mode {
+					CollectorMode::Store => {
+						*inventory.get_count_mut(molecule.variant) += 1;
+					}
+					CollectorMode::Sell => {
+						sell_molecule(molecule.variant, SaleSource::Collector, market, &mut power, &mut ledger);
+					}
+				}
+				// Flag so reactions this frame skip it
+				molecule.reacted = true;
+				commands.entity(entity).despawn_recursive();
+			}
+		}
+	}
+}
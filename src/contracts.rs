@@ -0,0 +1,351 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::UiAssets,
+	reactor_ui::ReactorUi,
+	economy::{Economy, Power, Clock},
+	grid::{Grid, GridLabel},
+	collector::Inventory,
+	enums::MoleculeType,
+	campaign::Campaign,
+	research::{Research, ResearchEffect, ReactorPart},
+	layout::WorldAnchor,
+	locale::Locale,
+};
+
+// ---------- PLUGINS ----------
+pub struct ContractPlugin;
+
+impl Plugin for ContractPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_system_set(SystemSet::on_enter(GameState::Reactor)
+			.with_system(generate_contracts)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(track_contracts.label(ContractLabel).after(GridLabel))
+			.with_system(button_contract)
+			.with_system(update_contract_text)
+		)
+		;
+	}
+}
+
+// Contracts closing at the end of the day pay out before the clock judges it
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContractLabel;
+
+// ---------- RESOURCES ----------
+const CONTRACTS_PER_DAY: usize = 3;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ContractKind {
+	// Hand over harvested molecules before the deadline hour
+	Deliver {
+		molecule_type: MoleculeType,
+		count: u32,
+		deadline: usize,
+	},
+	// Deliver at least this much to the grid every hour in [from, to)
+	Sustain {
+		per_hour: f32,
+		from: usize,
+		to: usize,
+	},
+}
+
+pub struct ContractTemplate {
+	// Locale key
+	pub client: &'static str,
+	pub kind: ContractKind,
+	pub reward: f32,
+	pub penalty: f32,
+}
+
+impl ContractTemplate {
+	// Deliveries need the molecule and collector ports to store it in
+	pub fn is_available(&self, research: &Research, campaign: &Campaign) -> bool {
+		match self.kind {
+			ContractKind::Deliver {molecule_type, ..} =>
+				research.molecule_unlocked(molecule_type)
+				&& research.is_unlocked(ResearchEffect::Part(ReactorPart::Collector))
+				&& !campaign.is_banned(molecule_type),
+			ContractKind::Sustain {..} => true,
+		}
+	}
+}
+
+// Power amounts are for the first day and scale with the day's target
+pub const CONTRACT_TEMPLATES: [ContractTemplate; 6] = [
+	ContractTemplate {
+		client: "client.paint_co",
+		kind: ContractKind::Deliver {molecule_type: MoleculeType::Purple, count: 5, deadline: 3},
+		reward: 150.0,
+		penalty: 50.0,
+	},
+	ContractTemplate {
+		client: "client.smelter",
+		kind: ContractKind::Deliver {molecule_type: MoleculeType::Orange, count: 5, deadline: 4},
+		reward: 400.0,
+		penalty: 100.0,
+	},
+	ContractTemplate {
+		client: "client.gravel_pit",
+		kind: ContractKind::Deliver {molecule_type: MoleculeType::Gray, count: 10, deadline: 6},
+		reward: 200.0,
+		penalty: 60.0,
+	},
+	ContractTemplate {
+		client: "client.hospital",
+		kind: ContractKind::Sustain {per_hour: 200.0, from: 1, to: 3},
+		reward: 250.0,
+		penalty: 150.0,
+	},
+	ContractTemplate {
+		client: "client.tram_line",
+		kind: ContractKind::Sustain {per_hour: 250.0, from: 5, to: 8},
+		reward: 350.0,
+		penalty: 200.0,
+	},
+	ContractTemplate {
+		client: "client.bakery",
+		kind: ContractKind::Sustain {per_hour: 120.0, from: 0, to: 2},
+		reward: 120.0,
+		penalty: 60.0,
+	},
+];
+
+// ---------- COMPONENTS ----------
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ContractStatus {
+	Open,
+	Fulfilled,
+	Failed,
+}
+
+#[derive(Component)]
+pub struct Contract {
+	pub client: &'static str,
+	pub kind: ContractKind,
+	pub reward: f32,
+	pub penalty: f32,
+	pub status: ContractStatus,
+}
+
+impl Contract {
+	pub fn describe(&self, locale: &Locale) -> String {
+		match self.kind {
+			ContractKind::Deliver {molecule_type, count, deadline} =>
+				locale.format("contract.deliver", &[
+					("client", &locale.get(self.client)),
+					("count", &count),
+					("molecule", &molecule_type.short_name(locale)),
+					("hour", &(deadline + 9)),
+				]),
+			ContractKind::Sustain {per_hour, from, to} =>
+				locale.format("contract.sustain", &[
+					("client", &locale.get(self.client)),
+					("rate", &format!("{per_hour:.0}")),
+					("from", &(from + 9)),
+					("to", &(to + 9)),
+				]),
+		}
+	}
+}
+
+#[derive(Component)]
+struct ContractButton(Entity);
+
+#[derive(Component)]
+struct ContractText(Entity);
+
+// ---------- SYSTEMS ----------
+fn generate_contracts (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	campaign_query: Query<&Campaign>,
+	research_query: Query<&Research>,
+) {
+	let campaign = campaign_query.single();
+	let research = research_query.single();
+	// Power amounts follow the day's target
+	let scale = campaign.plan().target_scale;
+
+	let available: Vec<&ContractTemplate> = CONTRACT_TEMPLATES
+		.iter()
+		.filter(|template| template.is_available(research, campaign))
+		.collect();
+	let contracts: Vec<Entity> = available
+		.choose_multiple(&mut rand::thread_rng(), CONTRACTS_PER_DAY)
+		.map(|template| {
+			let kind = match template.kind {
+				ContractKind::Sustain {per_hour, from, to} => ContractKind::Sustain {per_hour: per_hour * scale, from, to},
+				kind => kind,
+			};
+			commands
+				.spawn()
+				.insert(Contract {
+					client: template.client,
+					kind,
+					reward: template.reward * scale,
+					penalty: template.penalty * scale,
+					status: ContractStatus::Open,
+				})
+				.insert(Economy)
+				.insert(Name::new(format!("{} Contract", template.client)))
+				.id()
+		})
+		.collect();
+
+	// Board on the left under the molecule buttons
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				flex_direction: FlexDirection::ColumnReverse,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(-630.0, -240.0), Vec2::new(300.0, 200.0)))
+		.insert(Name::new("Contract Node"))
+		.insert(ReactorUi)
+		.with_children(|parent| {
+			for contract in contracts {
+
+				// Contract Button
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: Style {
+							size: Size::new(Val::Px(300.0), Val::Px(60.0)),
+							margin: UiRect::all(Val::Px(3.0)),
+							align_items: AlignItems::Center,
+							..default()
+						},
+						color: INVISIBLE.into(),
+						..default()
+					})
+					.insert(ContractButton(contract))
+					.with_children(|parent| {
+						parent
+							.spawn_bundle(TextBundle::from_section(
+								"",
+								TextStyle {
+									font: ui_assets.font.clone(),
+									font_size: 16.0,
+									color: Color::WHITE,
+								},
+							))
+							.insert(ContractText(contract));
+					});
+			}
+		});
+}
+
+fn settle_contract (
+	contract: &mut Contract,
+	power: &mut Power,
+	status: ContractStatus,
+) {
+	contract.status = status;
+	let adjustment = match status {
+		ContractStatus::Fulfilled => contract.reward,
+		ContractStatus::Failed => -contract.penalty,
+		ContractStatus::Open => 0.0,
+	};
+	power.current_power = (power.current_power + adjustment).clamp(0.0, 999000.0);
+}
+
+// Fails deliveries past their deadline and judges sustain orders once their window closes,
+// nothing is left open when the day ends
+fn track_contracts (
+	mut contract_query: Query<&mut Contract>,
+	mut economy_query: Query<(&Economy, &Clock, &mut Power)>,
+	grid_query: Query<&Grid>,
+) {
+	let (_economy, clock, mut power) = economy_query.single_mut();
+	let grid = grid_query.single();
+	let hour = clock.hour();
+	let day_over = clock.countdown.finished();
+
+	for mut contract in contract_query.iter_mut() {
+		if contract.status != ContractStatus::Open {
+			continue;
+		}
+		match contract.kind {
+			ContractKind::Deliver {deadline, ..} => {
+				if hour >= deadline || day_over {
+					settle_contract(&mut contract, &mut power, ContractStatus::Failed);
+				}
+			}
+			ContractKind::Sustain {per_hour, from, to} => {
+				// Fail as soon as one hour in the window comes up short
+				let short = grid.results.iter()
+					.filter(|result| result.hour >= from && result.hour < to)
+					.any(|result| result.delivered < per_hour);
+				if short {
+					settle_contract(&mut contract, &mut power, ContractStatus::Failed);
+				} else if grid.hour >= to || day_over {
+					settle_contract(&mut contract, &mut power, ContractStatus::Fulfilled);
+				}
+			}
+		}
+	}
+}
+
+// Clicking a delivery order hands over molecules from the inventory
+fn button_contract (
+	interaction_query: Query<(&ContractButton, &Interaction), Changed<Interaction>>,
+	mut contract_query: Query<&mut Contract>,
+	mut inventory_query: Query<&mut Inventory>,
+	mut economy_query: Query<(&Economy, &mut Power)>,
+) {
+	let mut inventory = inventory_query.single_mut();
+	let (_economy, mut power) = economy_query.single_mut();
+
+	for (button, interaction) in interaction_query.iter() {
+		if *interaction != Interaction::Clicked {
+			continue;
+		}
+		if let Ok(mut contract) = contract_query.get_mut(button.0) {
+			if let ContractKind::Deliver {molecule_type, count, ..} = contract.kind {
+				let stock = inventory.get_count_mut(molecule_type);
+				if contract.status == ContractStatus::Open && *stock >= count {
+					*stock -= count;
+					settle_contract(&mut contract, &mut power, ContractStatus::Fulfilled);
+				}
+			}
+		}
+	}
+}
+
+fn update_contract_text (
+	contract_query: Query<&Contract>,
+	inventory_query: Query<&Inventory>,
+	locale: Res<Locale>,
+	mut text_query: Query<(&ContractText, &mut Text)>,
+) {
+	let inventory = inventory_query.single();
+
+	for (contract_text, mut text) in text_query.iter_mut() {
+		if let Ok(contract) = contract_query.get(contract_text.0) {
+			let progress = match (contract.status, contract.kind) {
+				(ContractStatus::Open, ContractKind::Deliver {molecule_type, count, ..}) =>
+					format!("{}/{count} +{:.0} -{:.0}", inventory.get_count(molecule_type).min(&count), contract.reward, contract.penalty),
+				(ContractStatus::Open, ContractKind::Sustain {..}) =>
+					format!("+{:.0} -{:.0}", contract.reward, contract.penalty),
+				(ContractStatus::Fulfilled, _) => locale.get("contract.fulfilled").to_string(),
+				(ContractStatus::Failed, _) => locale.get("contract.failed").to_string(),
+			};
+			text.sections[0].value = format!("{}\n{progress}", contract.describe(&locale));
+			text.sections[0].style.color = match contract.status {
+				ContractStatus::Open => Color::WHITE,
+				ContractStatus::Fulfilled => Color::rgb(0.3, 0.9, 0.3),
+				ContractStatus::Failed => Color::rgb(0.9, 0.3, 0.3),
+			};
+		}
+	}
+}
@@ -0,0 +1,515 @@
+use std::{collections::HashMap, marker::PhantomData, time::Duration};
+
+use bevy::{prelude::*, ecs::system::SystemParam, ui::FocusPolicy};
+use serde::{Serialize, Deserialize};
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::{UiAssets, UiButton, OPTION_COLOR, CONFLICT_OPTION_COLOR, despawn_screen, show_buttons},
+	reactor_ui::ReactorUi,
+	enums::MoleculeType,
+	settings::Settings,
+	locale::Locale,
+	layout::WorldAnchor,
+};
+
+// ---------- PLUGINS ----------
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.insert_resource(GameSpeed {
+			paused: false,
+			fast: false,
+		})
+		.insert_resource(ControlsMenu {
+			capturing: None,
+			message: String::new(),
+			dirty: false,
+		})
+		.add_system_set(SystemSet::on_enter(GameState::Reactor)
+			.with_system(reset_game_speed)
+			.with_system(generate_speed_text)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(change_game_speed)
+			.with_system(update_speed_text)
+		)
+		// CONTROLS MENU
+		.add_system_set(SystemSet::on_enter(GameState::SettingsControlsMenu)
+			.with_system(open_controls_menu)
+		)
+		.add_system_set(SystemSet::on_update(GameState::SettingsControlsMenu)
+			.with_system(show_buttons)
+			// Reads input before the click that started a capture can be taken as the new binding
+			.with_system(capture_binding.before(button_binding))
+			.with_system(button_binding)
+			.with_system(button_controls_back)
+			.with_system(load_controls_menu.after(capture_binding).after(button_binding))
+		)
+		.add_system_set(SystemSet::on_exit(GameState::SettingsControlsMenu)
+			.with_system(despawn_screen::<ControlsMenuUi>)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+const FAST_SPEED: f32 = 2.0;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum InputAction {
+	ToggleGodMode,
+	Vent,
+	Pause,
+	SpeedUp,
+	// Cycles through the field emitter kinds
+	ToggleField,
+	ToggleDischarge,
+	TakeLoan,
+	// Arms the injector like clicking that molecule's cost button
+	SpawnMolecule(MoleculeType),
+}
+
+// In the order they're listed on the controls screen
+pub const INPUT_ACTIONS: [InputAction; 12] = [
+	InputAction::Pause,
+	InputAction::SpeedUp,
+	InputAction::Vent,
+	InputAction::ToggleField,
+	InputAction::ToggleDischarge,
+	InputAction::TakeLoan,
+	InputAction::ToggleGodMode,
+	InputAction::SpawnMolecule(MoleculeType::Red),
+	InputAction::SpawnMolecule(MoleculeType::Blue),
+	InputAction::SpawnMolecule(MoleculeType::Orange),
+	InputAction::SpawnMolecule(MoleculeType::Purple),
+	InputAction::SpawnMolecule(MoleculeType::Gray),
+];
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Binding {
+	Key(KeyCode),
+	Mouse(MouseButton),
+}
+
+// Already taken by aiming the injector and panning the camera
+const RESERVED_BINDINGS: [Binding; 2] = [
+	Binding::Mouse(MouseButton::Left),
+	Binding::Mouse(MouseButton::Right),
+];
+
+impl InputAction {
+	pub fn name(&self, locale: &Locale) -> String {
+		match self {
+			InputAction::ToggleGodMode => locale.get("controls.god_mode").to_string(),
+			InputAction::Vent => locale.get("controls.vent").to_string(),
+			InputAction::Pause => locale.get("controls.pause").to_string(),
+			InputAction::SpeedUp => locale.get("controls.speed_up").to_string(),
+			InputAction::ToggleField => locale.get("controls.field").to_string(),
+			InputAction::ToggleDischarge => locale.get("controls.discharge").to_string(),
+			InputAction::TakeLoan => locale.get("controls.loan").to_string(),
+			InputAction::SpawnMolecule(molecule_type) => locale.format("controls.spawn", &[("molecule", &molecule_type.name(locale))]),
+		}
+	}
+
+	pub fn default_binding(&self) -> Binding {
+		match self {
+			InputAction::ToggleGodMode => Binding::Key(KeyCode::G),
+			InputAction::Vent => Binding::Key(KeyCode::V),
+			InputAction::Pause => Binding::Key(KeyCode::P),
+			InputAction::SpeedUp => Binding::Key(KeyCode::Tab),
+			InputAction::ToggleField => Binding::Key(KeyCode::F),
+			InputAction::ToggleDischarge => Binding::Key(KeyCode::B),
+			InputAction::TakeLoan => Binding::Key(KeyCode::L),
+			InputAction::SpawnMolecule(MoleculeType::Red) => Binding::Key(KeyCode::Key1),
+			InputAction::SpawnMolecule(MoleculeType::Blue) => Binding::Key(KeyCode::Key2),
+			InputAction::SpawnMolecule(MoleculeType::Orange) => Binding::Key(KeyCode::Key3),
+			InputAction::SpawnMolecule(MoleculeType::Purple) => Binding::Key(KeyCode::Key4),
+			InputAction::SpawnMolecule(MoleculeType::Gray) => Binding::Key(KeyCode::Key5),
+		}
+	}
+}
+
+impl Binding {
+	pub fn label(&self) -> String {
+		match self {
+			Binding::Key(key) => format!("{key:?}"),
+			Binding::Mouse(button) => format!("Mouse {button:?}"),
+		}
+	}
+}
+
+// Only bindings that differ from the defaults need to be in the file
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct ControlSettings {
+	pub bindings: HashMap<InputAction, Binding>,
+}
+
+impl ControlSettings {
+	pub fn binding(&self, action: InputAction) -> Binding {
+		self.bindings.get(&action).copied().unwrap_or_else(|| action.default_binding())
+	}
+
+	// Another action already using this binding
+	pub fn conflict(&self, action: InputAction, binding: Binding) -> Option<InputAction> {
+		INPUT_ACTIONS.iter()
+			.copied()
+			.find(|other| *other != action && self.binding(*other) == binding)
+	}
+}
+
+// Read instead of raw keyboard and mouse input so everything follows the player's bindings
+#[derive(SystemParam)]
+pub struct Actions<'w, 's> {
+	settings: Res<'w, Settings>,
+	keyboard: Res<'w, Input<KeyCode>>,
+	mouse: Res<'w, Input<MouseButton>>,
+	#[system_param(ignore)]
+	marker: PhantomData<&'s ()>,
+}
+
+impl<'w, 's> Actions<'w, 's> {
+	pub fn just_pressed(&self, action: InputAction) -> bool {
+		match self.settings.controls.binding(action) {
+			Binding::Key(key) => self.keyboard.just_pressed(key),
+			Binding::Mouse(button) => self.mouse.just_pressed(button),
+		}
+	}
+}
+
+// How fast the reactor day runs, every simulation system scales its time step by this
+pub struct GameSpeed {
+	pub paused: bool,
+	pub fast: bool,
+}
+
+impl GameSpeed {
+	pub fn scale(&self) -> f32 {
+		if self.paused {0.0} else if self.fast {FAST_SPEED} else {1.0}
+	}
+
+	pub fn delta(&self, time: &Time) -> Duration {
+		time.delta().mul_f32(self.scale())
+	}
+
+	pub fn delta_seconds(&self, time: &Time) -> f32 {
+		time.delta_seconds() * self.scale()
+	}
+}
+
+// Set while the controls screen is open
+struct ControlsMenu {
+	// Action waiting for its new key
+	capturing: Option<InputAction>,
+	// Conflict or hint shown under the list
+	message: String,
+	dirty: bool,
+}
+
+// ---------- COMPONENTS ----------
+#[derive(Component)]
+struct SpeedText;
+
+#[derive(Component)]
+struct ControlsMenuUi;
+
+#[derive(Component)]
+struct BindingButton(InputAction);
+
+#[derive(Component)]
+struct ControlsBackButton;
+
+// ---------- SYSTEMS ----------
+fn reset_game_speed (
+	mut speed: ResMut<GameSpeed>,
+) {
+	speed.paused = false;
+	speed.fast = false;
+}
+
+fn change_game_speed (
+	actions: Actions,
+	mut speed: ResMut<GameSpeed>,
+) {
+	if actions.just_pressed(InputAction::Pause) {
+		speed.paused = !speed.paused;
+	}
+	if actions.just_pressed(InputAction::SpeedUp) {
+		speed.fast = !speed.fast;
+	}
+}
+
+fn generate_speed_text (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+) {
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				justify_content: JustifyContent::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(0.0, 270.0), Vec2::new(300.0, 40.0)))
+		.insert(Name::new("Speed Node"))
+		.insert(ReactorUi)
+		.with_children(|parent| {
+			parent
+				.spawn_bundle(TextBundle::from_section(
+					"",
+					TextStyle {
+						font: ui_assets.font.clone(),
+						font_size: 32.0,
+						color: ui_assets.text_color,
+					},
+				))
+				.insert(SpeedText);
+		});
+}
+
+fn update_speed_text (
+	speed: Res<GameSpeed>,
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	mut text_query: Query<&mut Text, With<SpeedText>>,
+) {
+	if !speed.is_changed() {
+		return;
+	}
+	for mut text in text_query.iter_mut() {
+		text.sections[0].value = if speed.paused {
+			locale.format("controls.paused", &[("key", &settings.controls.binding(InputAction::Pause).label())])
+		} else if speed.fast {
+			locale.format("controls.speed", &[("speed", &format!("{FAST_SPEED:.0}"))])
+		} else {
+			String::new()
+		};
+	}
+}
+
+fn open_controls_menu (
+	locale: Res<Locale>,
+	mut controls_menu: ResMut<ControlsMenu>,
+) {
+	controls_menu.capturing = None;
+	controls_menu.message = locale.get("controls.hint").to_string();
+	controls_menu.dirty = true;
+}
+
+// Rebuilt whenever a binding changes or a capture starts
+fn load_controls_menu (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	mut controls_menu: ResMut<ControlsMenu>,
+	ui_query: Query<Entity, With<ControlsMenuUi>>,
+) {
+	if !controls_menu.dirty {
+		return;
+	}
+	controls_menu.dirty = false;
+	for entity in ui_query.iter() {
+		commands.entity(entity).despawn_recursive();
+	}
+
+	let text_style = TextStyle {
+		font: ui_assets.font.clone(),
+		font_size: 28.0,
+		color: ui_assets.text_color,
+	};
+
+	commands
+		// Node Bundle to hold the Controls Menu UI elements
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				margin: UiRect::all(Val::Auto),
+				flex_direction: FlexDirection::ColumnReverse,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(ControlsMenuUi)
+		.insert(Name::new("Controls Menu UI"))
+		.with_children(|parent| {
+
+			// Binding Rows
+			for action in INPUT_ACTIONS {
+				let binding = settings.controls.binding(action);
+				let conflicted = settings.controls.conflict(action, binding).is_some();
+				let label = if controls_menu.capturing == Some(action) {
+					locale.get("controls.press_key").to_string()
+				} else {
+					binding.label()
+				};
+
+				parent
+					.spawn_bundle(NodeBundle {
+						style: Style {
+							size: Size::new(Val::Px(600.0), Val::Px(44.0)),
+							justify_content: JustifyContent::SpaceBetween,
+							align_items: AlignItems::Center,
+							..default()
+						},
+						color: INVISIBLE.into(),
+						..default()
+					})
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							action.name(&locale),
+							text_style.clone(),
+						));
+
+						// Binding Button, red while it clashes with another action
+						parent
+							.spawn_bundle(ButtonBundle {
+								style: Style {
+									size: Size::new(Val::Px(250.0), Val::Px(40.0)),
+									justify_content: JustifyContent::Center,
+									align_items: AlignItems::Center,
+									..default()
+								},
+								color: if conflicted {CONFLICT_OPTION_COLOR.into()} else {OPTION_COLOR.into()},
+								..default()
+							})
+							.insert(BindingButton(action))
+							.with_children(|parent| {
+								parent.spawn_bundle(TextBundle::from_section(
+									label,
+									text_style.clone(),
+								));
+							});
+					});
+			}
+
+			// Message
+			parent.spawn_bundle(
+				TextBundle::from_section(
+					controls_menu.message.clone(),
+					TextStyle {
+						font: ui_assets.font.clone(),
+						font_size: 22.0,
+						color: ui_assets.text_color,
+					},
+				)
+				.with_style(Style {
+					margin: UiRect::all(Val::Px(10.0)),
+					..default()
+				}),
+			);
+
+			// Back Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: Style {
+						size: Size::new(Val::Px(250.0), Val::Px(65.0)),
+						margin: UiRect::all(Val::Px(20.0)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					color: ui_assets.button_color.into(),
+					..default()
+				})
+				.insert(ControlsBackButton)
+				.insert(UiButton)
+				.with_children(|parent| {
+
+					// Back Button Sprite
+					parent
+						.spawn_bundle(ImageBundle {
+							style: Style {
+								size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+								justify_content: JustifyContent::Center,
+								align_items: AlignItems::Center,
+								..Default::default()
+							},
+						image: ui_assets.button_idle.clone().into(),
+						..Default::default()
+					})
+					.insert(FocusPolicy::Pass)
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							locale.get("menu.back"),
+							TextStyle {
+								font: ui_assets.font.clone(),
+								font_size: 40.0,
+								color: ui_assets.text_color,
+							},
+						));
+					});
+				});
+		});
+}
+
+fn button_binding (
+	interaction_query: Query<(&BindingButton, &Interaction), Changed<Interaction>>,
+	locale: Res<Locale>,
+	mut controls_menu: ResMut<ControlsMenu>,
+) {
+	for (button, interaction) in interaction_query.iter() {
+		if *interaction == Interaction::Clicked && controls_menu.capturing.is_none() {
+			controls_menu.capturing = Some(button.0);
+			controls_menu.message = locale.get("controls.escape_cancels").to_string();
+			controls_menu.dirty = true;
+		}
+	}
+}
+
+// Takes the first key or mouse button pressed, unless another action or the game already uses it
+fn capture_binding (
+	keyboard: Res<Input<KeyCode>>,
+	mouse: Res<Input<MouseButton>>,
+	mut settings: ResMut<Settings>,
+	locale: Res<Locale>,
+	mut controls_menu: ResMut<ControlsMenu>,
+) {
+	let action = match controls_menu.capturing {
+		Some(action) => action,
+		None => return,
+	};
+
+	if keyboard.just_pressed(KeyCode::Escape) {
+		controls_menu.capturing = None;
+		controls_menu.message = String::new();
+		controls_menu.dirty = true;
+		return;
+	}
+
+	let binding = match (keyboard.get_just_pressed().next(), mouse.get_just_pressed().next()) {
+		(Some(key), _) => Binding::Key(*key),
+		(None, Some(button)) => Binding::Mouse(*button),
+		(None, None) => return,
+	};
+
+	controls_menu.capturing = None;
+	controls_menu.dirty = true;
+	if RESERVED_BINDINGS.contains(&binding) {
+		controls_menu.message = locale.format("controls.reserved", &[("key", &binding.label())]);
+	} else if let Some(other) = settings.controls.conflict(action, binding) {
+		controls_menu.message = locale.format("controls.conflict", &[("key", &binding.label()), ("action", &other.name(&locale))]);
+	} else {
+		controls_menu.message = locale.format("controls.bound", &[("action", &action.name(&locale)), ("key", &binding.label())]);
+		settings.controls.bindings.insert(action, binding);
+	}
+}
+
+fn button_controls_back (
+	interaction_query: Query<&Interaction, (Changed<Interaction>, With<ControlsBackButton>)>,
+	controls_menu: Res<ControlsMenu>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for interaction in interaction_query.iter() {
+		if *interaction == Interaction::Clicked && controls_menu.capturing.is_none() {
+			state.pop().expect("Failed to change states");
+		}
+	}
+}
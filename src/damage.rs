@@ -0,0 +1,248 @@
+use bevy::prelude::*;
+use rand::random;
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::{despawn_screen, UiAssets},
+	audio::Volume,
+	reactor::{Molecule, GodMode},
+	reactor_ui::{Reactor, ReactorUi, REACTOR_CENTER, REACTOR_SIZE},
+	shop::Upgrades,
+	economy::{Economy, Power, Integrity},
+	endgame::BasicCountdown,
+	controls::GameSpeed,
+	layout::WorldAnchor,
+	locale::Locale,
+};
+
+// ---------- PLUGINS ----------
+pub struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_system_set(SystemSet::on_enter(GameState::Reactor)
+			.with_system(generate_damage_overlay)
+			.with_system(generate_repair_button)
+		)
+		.add_system_set(SystemSet::on_exit(GameState::Reactor)
+			.with_system(despawn_screen::<DamageOverlay>)
+		)
+		.add_system_set(SystemSet::on_pause(GameState::Reactor)
+			.with_system(despawn_screen::<DamageOverlay>)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(update_damage_overlay)
+			.with_system(damage_effects)
+			.with_system(update_integrity_text)
+			.with_system(button_repair)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+const REPAIR_COST: f32 = 100.0;
+const REPAIR_AMOUNT: f32 = 25.0;
+// Chance for each molecule to escape per alarm tick once the reactor is leaking
+const LEAK_CHANCE: f32 = 0.1;
+const ALARM_INTERVAL: f32 = 2.0;
+
+// ---------- COMPONENTS ----------
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DamageStage {
+	Intact,
+	Cracked,
+	Alarm,
+	Leaking,
+}
+
+impl DamageStage {
+	pub fn from_integrity(integrity: &Integrity) -> DamageStage {
+		let fraction = integrity.current_integrity / integrity.max_integrity;
+		if fraction > 0.75 {
+			DamageStage::Intact
+		} else if fraction > 0.5 {
+			DamageStage::Cracked
+		} else if fraction > 0.25 {
+			DamageStage::Alarm
+		} else {
+			DamageStage::Leaking
+		}
+	}
+}
+
+#[derive(Component)]
+struct DamageOverlay;
+
+#[derive(Component)]
+struct AlarmTimer;
+
+#[derive(Component)]
+struct IntegrityText;
+
+#[derive(Component)]
+struct RepairButton;
+
+// ---------- SYSTEMS ----------
+fn generate_damage_overlay (
+	mut commands: Commands,
+	upgrades_query: Query<&Upgrades>,
+) {
+	let upgrades = upgrades_query.single();
+
+	// Red wash over the reactor that deepens as it cracks
+	commands
+		.spawn_bundle(SpriteBundle {
+			sprite: Sprite {
+				color: Color::rgba(1.0, 0.0, 0.0, 0.0),
+				custom_size: Some(REACTOR_SIZE * upgrades.size_scale()),
+				..default()
+			},
+			transform: Transform::from_translation(REACTOR_CENTER.extend(940.0)),
+			..default()
+		})
+		.insert(Name::new("Damage Overlay"))
+		.insert(DamageOverlay);
+
+	commands
+		.spawn()
+		.insert(BasicCountdown(Timer::from_seconds(ALARM_INTERVAL, true)))
+		.insert(Name::new("Alarm Timer"))
+		.insert(Reactor)
+		.insert(AlarmTimer);
+}
+
+fn generate_repair_button (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
+) {
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(645.0, 390.0), Vec2::new(160.0, 48.0)))
+		.insert(Name::new("Repair Node"))
+		.insert(ReactorUi)
+		.with_children(|parent| {
+
+			// Repair Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: Style {
+						size: Size::new(Val::Px(160.0), Val::Px(48.0)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					color: INVISIBLE.into(),
+					..default()
+				})
+				.insert(RepairButton)
+				.with_children(|parent| {
+					parent
+						.spawn_bundle(TextBundle::from_section(
+							locale.format("damage.hull", &[("percent", &100)]),
+							TextStyle {
+								font: ui_assets.font.clone(),
+								font_size: 24.0,
+								color: ui_assets.text_color,
+							},
+						))
+						.insert(IntegrityText);
+				});
+		});
+}
+
+fn update_damage_overlay (
+	economy_query: Query<(&Economy, &Integrity), Changed<Integrity>>,
+	mut overlay_query: Query<&mut Sprite, With<DamageOverlay>>,
+) {
+	for (_economy, integrity) in economy_query.iter() {
+		let mut overlay = overlay_query.single_mut();
+		let damage = 1.0 - integrity.current_integrity / integrity.max_integrity;
+		overlay.color.set_a(match DamageStage::from_integrity(integrity) {
+			DamageStage::Intact => 0.0,
+			_ => damage * 0.35,
+		});
+	}
+}
+
+fn damage_effects (
+	mut commands: Commands,
+	time: Res<Time>,
+	speed: Res<GameSpeed>,
+	asset_server: Res<AssetServer>,
+	audio: Res<Audio>,
+	volume: Res<Volume>,
+	mut alarm_query: Query<&mut BasicCountdown, With<AlarmTimer>>,
+	economy_query: Query<(&Economy, &Integrity)>,
+	mut molecule_query: Query<(Entity, &mut Molecule)>,
+	godmode_query: Query<&GodMode>,
+) {
+	let mut alarm = alarm_query.single_mut();
+	let (_economy, integrity) = economy_query.single();
+	let godmode = godmode_query.single();
+
+	if godmode.0 || !alarm.0.tick(speed.delta(&time)).just_finished() {
+		return;
+	}
+
+	let stage = DamageStage::from_integrity(integrity);
+	if stage == DamageStage::Alarm || stage == DamageStage::Leaking {
+		audio.play_with_settings(asset_server.load("audio/beep_boop.ogg"), PlaybackSettings {
+			repeat: false,
+			volume: volume.sfx,
+			speed: 1.0,
+		});
+	}
+	if stage == DamageStage::Leaking {
+		for (entity, mut molecule) in molecule_query.iter_mut() {
+			if !molecule.reacted && random::<f32>() < LEAK_CHANCE {
+				molecule.reacted = true;
+				commands.entity(entity).despawn_recursive();
+			}
+		}
+	}
+}
+
+fn update_integrity_text (
+	economy_query: Query<(&Economy, &Integrity)>,
+	mut text_query: Query<&mut Text, With<IntegrityText>>,
+	locale: Res<Locale>,
+) {
+	let (_economy, integrity) = economy_query.single();
+	let mut text = text_query.single_mut();
+
+	let percent = format!("{:.0}", integrity.current_integrity / integrity.max_integrity * 100.0);
+	text.sections[0].value = match DamageStage::from_integrity(integrity) {
+		DamageStage::Intact => locale.format("damage.hull", &[("percent", &percent)]),
+		_ => locale.format("damage.repair", &[("percent", &percent)]),
+	};
+	text.sections[0].style.color = match DamageStage::from_integrity(integrity) {
+		DamageStage::Intact | DamageStage::Cracked => Color::rgb(0.0, 0.3, 0.0),
+		DamageStage::Alarm | DamageStage::Leaking => Color::rgb(0.8, 0.0, 0.0),
+	};
+}
+
+fn button_repair (
+	interaction_query: Query<(&RepairButton, &Interaction), Changed<Interaction>>,
+	mut economy_query: Query<(&Economy, &mut Power, &mut Integrity)>,
+) {
+	let (_economy, mut power, mut integrity) = economy_query.single_mut();
+
+	for (_, interaction) in interaction_query.iter() {
+		if *interaction == Interaction::Clicked
+		&& integrity.current_integrity < integrity.max_integrity
+		&& power.current_power > REPAIR_COST {
+			power.current_power -= REPAIR_COST;
+			integrity.current_integrity = (integrity.current_integrity + REPAIR_AMOUNT).min(integrity.max_integrity);
+		}
+	}
+}
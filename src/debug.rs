@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy_inspector_egui::{WorldInspectorPlugin, RegisterInspectable};
 
-use crate::{reactor::{Molecule, Dimensions, Velocity, Unlocked}, camera::PanCamera, economy::{Power, Temperature}, audio::Volume};
+use crate::{reactor::{Molecule, Dimensions, Velocity, ReactorWalls}, camera::PanCamera, economy::{Power, Temperature, Integrity}, audio::Volume, collector::Inventory, battery::Battery};
 
 pub struct DebugPlugin;
 
@@ -15,9 +15,12 @@ impl Plugin for DebugPlugin {
 			.register_inspectable::<Velocity>()
 			.register_inspectable::<Power>()
 			.register_inspectable::<Temperature>()
+			.register_inspectable::<Integrity>()
 			.register_inspectable::<PanCamera>()
             .register_inspectable::<Volume>()
-            .register_inspectable::<Unlocked>()
+			.register_inspectable::<Inventory>()
+			.register_inspectable::<ReactorWalls>()
+			.register_inspectable::<Battery>()
 			;
 		}
 	}
@@ -1,7 +1,26 @@
 use bevy::prelude::*;
 use bevy_inspector_egui::Inspectable;
+use rand::random;
+use serde::{Serialize, Deserialize};
 
-use crate::{start_menu::despawn_screen, GameState, reactor::GodMode};
+use crate::{
+	GameState,
+	start_menu::despawn_screen,
+	reactor::{GodMode, ReactorWalls, Cost, Dimensions, Molecule},
+	reactor_ui::{Reactor, REACTOR_SIZE},
+	enums::MoleculeType,
+	campaign::Campaign,
+	shop::Upgrades,
+	research::{Research, ResearchEffect},
+	grid::{GridLabel, HOURS},
+	contracts::ContractLabel,
+	loans::Debt,
+	fields::FieldEmitter,
+	collector::Collector,
+	battery::BatteryLabel,
+	balance::BalanceConfig,
+	controls::GameSpeed,
+};
 
 // ---------- PLUGINS ----------
 pub struct EconomyPlugin;
@@ -9,6 +28,7 @@ pub struct EconomyPlugin;
 impl Plugin for EconomyPlugin {
 	fn build(&self, app: &mut App) {
 		app
+		.add_startup_system(generate_market)
 		.add_system_set(SystemSet::on_enter(GameState::Reactor)
 				.with_system(begin_day)
 		)
@@ -21,25 +41,36 @@ impl Plugin for EconomyPlugin {
 		.add_system_set(SystemSet::on_resume(GameState::Reactor)
 		)
 		.add_system_set(SystemSet::on_update(GameState::Reactor)
-				.with_system(tick_clock)
+				// The hour moves on before the battery, loans and grid settle it
+				.with_system(tick_clock.before(BatteryLabel).before(GridLabel))
+				.with_system(judge_day.after(GridLabel).after(ContractLabel))
+				.with_system(update_market)
 		)
 		;
 	}
 }
 
 // ---------- RESOURCES ----------
-const START_POWER: f32 = 100.0;
-pub const TARGET_POWER: f32 = 2000.0;
-
 const START_PRESSURE: f32 = 0.0;
-pub const MAX_PRESSURE: f32 = 50.0;
-// Controls the averaging of the pressure [0-1]
-// too high values will lead to the pressure bouncing around unpredictably and killing the player instantly
-// too low values will make the thermometer lag behind whatever the real value
-pub const PRESSURE_SENSITIVITY: f32 = 0.05;
 
-// Game length in seconds
-const DAY_LENGTH: f32 = 180.0;
+pub const MAX_INTEGRITY: f32 = 100.0;
+
+// Seconds for extra demand to fall back by ~63%
+const DEMAND_RECOVERY: f32 = 20.0;
+// How often prices drift and trends are measured, in seconds
+const MARKET_TICK: f32 = 1.0;
+// Largest random step per tick and total drift away from the base price
+const DRIFT_STEP: f32 = 0.04;
+const MAX_DRIFT: f32 = 0.2;
+
+// Operating costs in power per second
+// Base upkeep of a reactor at the starting size, scales with its area
+const REACTOR_UPKEEP: f32 = 1.0;
+const MOLECULE_UPKEEP: f32 = 0.02;
+const FIELD_UPKEEP: f32 = 0.5;
+const COLLECTOR_UPKEEP: f32 = 0.25;
+// Seconds for the income rate shown on the monitor to catch up with a change
+pub const INCOME_SMOOTHING: f32 = 2.0;
 
 
 // ---------- COMPONENTS ----------
@@ -50,6 +81,8 @@ pub struct Economy;
 pub struct Power {
 	pub current_power: f32,
 	pub target_power: f32,
+	// Fed to the grid by the battery since the grid last took a delivery
+	pub generated: f32,
 }
 
 #[derive(Component, Inspectable)]
@@ -59,51 +92,285 @@ pub struct Temperature {
 	pub thermometer_sprite: usize,
 }
 
+// Drains while the reactor is over pressure, meltdown at zero
+#[derive(Component, Inspectable)]
+pub struct Integrity {
+	pub current_integrity: f32,
+	pub max_integrity: f32,
+}
+
+// Running rates in power per second, for the monitor
+#[derive(Component, Default)]
+pub struct CashFlow {
+	pub income: f32,
+	pub reactor_upkeep: f32,
+	pub molecule_upkeep: f32,
+	pub equipment_upkeep: f32,
+}
+
+impl CashFlow {
+	pub fn expenses(&self) -> f32 {
+		self.reactor_upkeep + self.molecule_upkeep + self.equipment_upkeep
+	}
+
+	pub fn net(&self) -> f32 {
+		self.income - self.expenses()
+	}
+}
+
 #[derive(Component)]
 pub struct Clock {
 	pub countdown: Timer,
 	pub time_sprite: usize,
 }
 
+impl Clock {
+	// Whole hours since 9:00, HOURS once the day is over
+	pub fn hour(&self) -> usize {
+		if self.countdown.finished() {
+			HOURS
+		} else {
+			(((1.0 - self.countdown.percent_left()) * HOURS as f32) as usize).min(HOURS - 1)
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum Trend {
+	Rising,
+	Falling,
+	Steady,
+}
+
+impl Trend {
+	pub fn arrow(&self) -> &'static str {
+		match self {
+			Trend::Rising => "^",
+			Trend::Falling => "v",
+			Trend::Steady => "-",
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct MarketEntry {
+	// 1.0 is no extra demand, multiplied by the balance cost scaling per purchase
+	pub demand: f32,
+	// Random walk around the base price, within +-MAX_DRIFT
+	pub drift: f32,
+	pub last_price: f32,
+	pub trend: Trend,
+}
+
+impl MarketEntry {
+	fn new(molecule_type: MoleculeType) -> MarketEntry {
+		MarketEntry {
+			demand: 1.0,
+			drift: 0.0,
+			last_price: molecule_type.base_cost(),
+			trend: Trend::Steady,
+		}
+	}
+}
+
+// Kept between days so prices remember how hard the player has been buying
+#[derive(Component)]
+pub struct Market {
+	pub red_entry: MarketEntry,
+	pub blue_entry: MarketEntry,
+	pub orange_entry: MarketEntry,
+	pub purple_entry: MarketEntry,
+	pub gray_entry: MarketEntry,
+	pub tick: Timer,
+}
+
+impl Market {
+	pub fn get_entry(&self, molecule_type: MoleculeType) -> &MarketEntry {
+		match molecule_type {
+			MoleculeType::Red => &self.red_entry,
+			MoleculeType::Blue => &self.blue_entry,
+			MoleculeType::Orange => &self.orange_entry,
+			MoleculeType::Purple => &self.purple_entry,
+			MoleculeType::Gray => &self.gray_entry,
+		}
+	}
+
+	pub fn get_entry_mut(&mut self, molecule_type: MoleculeType) -> &mut MarketEntry {
+		match molecule_type {
+			MoleculeType::Red => &mut self.red_entry,
+			MoleculeType::Blue => &mut self.blue_entry,
+			MoleculeType::Orange => &mut self.orange_entry,
+			MoleculeType::Purple => &mut self.purple_entry,
+			MoleculeType::Gray => &mut self.gray_entry,
+		}
+	}
+
+	pub fn price(&self, molecule_type: MoleculeType) -> f32 {
+		let entry = self.get_entry(molecule_type);
+		molecule_type.base_cost() * entry.demand * (1.0 + entry.drift)
+	}
+
+	pub fn record_purchase(&mut self, molecule_type: MoleculeType, cost_scaling: f32) {
+		self.get_entry_mut(molecule_type).demand *= cost_scaling;
+	}
+}
+
 // ---------- SYSTEMS ----------
+fn generate_market (
+	mut commands: Commands,
+) {
+	commands
+		.spawn()
+		.insert(Market {
+			red_entry: MarketEntry::new(MoleculeType::Red),
+			blue_entry: MarketEntry::new(MoleculeType::Blue),
+			orange_entry: MarketEntry::new(MoleculeType::Orange),
+			purple_entry: MarketEntry::new(MoleculeType::Purple),
+			gray_entry: MarketEntry::new(MoleculeType::Gray),
+			tick: Timer::from_seconds(MARKET_TICK, true),
+		})
+		.insert(Name::new("Market"));
+}
+
 fn begin_day(
 	mut commands: Commands,
+	walls_query: Query<&ReactorWalls>,
+	mut campaign_query: Query<&mut Campaign>,
+	mut research_query: Query<&mut Research>,
+	upgrades_query: Query<&Upgrades>,
+	balance: Res<BalanceConfig>,
 ) {
+	let walls = walls_query.single();
+	let upgrades = upgrades_query.single();
+	let mut campaign = campaign_query.single_mut();
+	let mut research = research_query.single_mut();
+
+	campaign.finished = false;
+	for molecule_type in campaign.plan().unlocks {
+		research.grant(ResearchEffect::Molecule(*molecule_type));
+	}
+
 	commands
 		.spawn()
 		.insert(Name::new("Economy"))
 		.insert(Economy)
 		.insert(Power {
-			current_power: START_POWER,
-			target_power: TARGET_POWER,
+			current_power: balance.start_power,
+			target_power: balance.target_power * campaign.plan().target_scale,
+			generated: 0.0,
 		})
 		.insert(Temperature {
 			current_pressure: START_PRESSURE,
-			max_pressure: MAX_PRESSURE,
+			max_pressure: balance.max_pressure * walls.material.pressure_tolerance() * campaign.pressure_scale() * upgrades.pressure_scale(),
 			thermometer_sprite: 0,
 		})
+		.insert(Integrity {
+			current_integrity: MAX_INTEGRITY,
+			max_integrity: MAX_INTEGRITY,
+		})
 		.insert(Clock {
-			countdown: Timer::from_seconds(DAY_LENGTH, false),
+			countdown: Timer::from_seconds(balance.day_length * campaign.day_length_scale(), false),
 			time_sprite: 0,
-		});
+		})
+		.insert(CashFlow::default());
 }
 
 fn tick_clock (
 	time: Res<Time>,
-	mut clock_query: Query<(&Economy, &mut Clock, &Power)>,
-	mut state: ResMut<State<GameState>>,
-    godmode_query: Query<&GodMode>
+	speed: Res<GameSpeed>,
+	mut clock_query: Query<(&Economy, &mut Clock, &mut Power, &mut CashFlow)>,
+    godmode_query: Query<&GodMode>,
+	reactor_query: Query<(&Reactor, &Dimensions)>,
+	molecule_query: Query<&Molecule>,
+	field_query: Query<&FieldEmitter>,
+	collector_query: Query<&Collector>,
 ) {
-	let (_, mut clock, money) = clock_query.single_mut();
+	let (_, mut clock, mut money, mut cash_flow) = clock_query.single_mut();
     let godmode = godmode_query.single();
     if !godmode.0 {
-        if clock.countdown.tick(time.delta()).just_finished() {
-            if money.current_power > money.target_power {
-                state.push(GameState::WinScreen).expect("Failed to change states");
-            }
-            else {
-                state.push(GameState::LoseScreen).expect("Failed to change states");
-            }
-        }
+		// Upkeep for the vessel, everything in it and the equipment running
+		let (_reactor, dimensions) = reactor_query.single();
+		cash_flow.reactor_upkeep = REACTOR_UPKEEP * dimensions.x_size * dimensions.y_size / (REACTOR_SIZE.x * REACTOR_SIZE.y);
+		cash_flow.molecule_upkeep = MOLECULE_UPKEEP * molecule_query.iter().count() as f32;
+		cash_flow.equipment_upkeep = FIELD_UPKEEP * field_query.iter().count() as f32
+			+ COLLECTOR_UPKEEP * collector_query.iter().filter(|collector| collector.accepts.is_some()).count() as f32;
+		money.current_power = (money.current_power - cash_flow.expenses() * speed.delta_seconds(&time)).max(0.0);
+
+        clock.countdown.tick(speed.delta(&time));
     }
 }
+
+// Runs once the grid has settled the last hour and the contracts have closed
+fn judge_day (
+	economy_query: Query<(&Economy, &Clock, &Power)>,
+	mut state: ResMut<State<GameState>>,
+	mut campaign_query: Query<&mut Campaign>,
+	mut upgrades_query: Query<&mut Upgrades>,
+	debt_query: Query<&Debt>,
+) {
+	let (_, clock, money) = economy_query.single();
+	if !clock.countdown.just_finished() {
+		return;
+	}
+
+	// Unpaid debt comes out of the day's result
+	let net_power = money.current_power - debt_query.single().balance;
+	if net_power > money.target_power {
+		campaign_query.single_mut().complete_day(net_power, money.target_power);
+		// Power left over past the target is banked for the shop
+		upgrades_query.single_mut().credits += net_power - money.target_power;
+		// Replace the reactor so the day summary can pop straight back to the Lab
+		state.set(GameState::WinScreen).expect("Failed to change states");
+	}
+	else {
+		state.push(GameState::LoseScreen).expect("Failed to change states");
+	}
+}
+
+fn update_market (
+	time: Res<Time>,
+	speed: Res<GameSpeed>,
+	mut market_query: Query<&mut Market>,
+	mut cost_query: Query<&mut Cost>,
+	upgrades_query: Query<&Upgrades>,
+) {
+	let mut market = market_query.single_mut();
+	let mut cost = cost_query.single_mut();
+	let upgrades = upgrades_query.single();
+
+	let molecule_types = [
+		MoleculeType::Red,
+		MoleculeType::Blue,
+		MoleculeType::Orange,
+		MoleculeType::Purple,
+		MoleculeType::Gray,
+	];
+
+	// Extra demand recovers continuously
+	let recovery = (-speed.delta_seconds(&time) / DEMAND_RECOVERY).exp();
+	for molecule_type in molecule_types {
+		let entry = market.get_entry_mut(molecule_type);
+		entry.demand = 1.0 + (entry.demand - 1.0) * recovery;
+	}
+
+	if market.tick.tick(speed.delta(&time)).just_finished() {
+		for molecule_type in molecule_types {
+			let price = market.price(molecule_type);
+			let entry = market.get_entry_mut(molecule_type);
+			entry.drift = (entry.drift + (2.0 * random::<f32>() - 1.0) * DRIFT_STEP).clamp(-MAX_DRIFT, MAX_DRIFT);
+			// Ignore tiny wobbles so the arrow doesn't flicker
+			entry.trend = if price > entry.last_price * 1.01 {
+				Trend::Rising
+			} else if price < entry.last_price * 0.99 {
+				Trend::Falling
+			} else {
+				Trend::Steady
+			};
+			entry.last_price = price;
+		}
+	}
+
+	for molecule_type in molecule_types {
+		*cost.get_cost_mut(molecule_type) = market.price(molecule_type) * upgrades.cost_scale();
+	}
+}
@@ -1,6 +1,17 @@
 use bevy::prelude::*;
 
-use crate::{GameState, start_menu::despawn_screen, audio::LoopTimer, reactor::MoleculeList, economy::Economy};
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::{UiAssets, despawn_screen},
+	audio::LoopTimer,
+	reactor::MoleculeList,
+	economy::Economy,
+	campaign::Campaign,
+	locale::Locale,
+	layout::WorldAnchor,
+	controls::InputAction,
+	settings::Settings,
+};
 
 // ---------- PLUGINS ----------
 pub struct EndgamePlugin;
@@ -21,8 +32,12 @@ impl Plugin for EndgamePlugin {
 			.with_system(despawn_screen::<EndgameSprites>)
 			.with_system(despawn_screen::<EndgameTimer>)
 		)
+		.add_system_set(SystemSet::on_exit(GameState::WinScreen)
+			.with_system(despawn_screen::<EndgameSprites>)
+			.with_system(despawn_screen::<EndgameTimer>)
+		)
 		.add_system_set(SystemSet::on_update(GameState::WinScreen)
-			.with_system(cut_to_credits)
+			.with_system(cut_to_summary)
 		)
 
 		.add_system_set(SystemSet::on_enter(GameState::LoseScreen)
@@ -66,6 +81,11 @@ impl Plugin for EndgamePlugin {
 	}
 }
 
+// ---------- RESOURCES ----------
+// The two colours of the endgame art
+const ENDGAME_BACKGROUND: Color = Color::rgb(0.137, 0.125, 0.106);
+const ENDGAME_TEXT_COLOR: Color = Color::rgb(0.773, 0.651, 0.463);
+
 // ---------- COMPONENTS ----------
 #[derive(Component)]
 struct EndgameSprites;
@@ -94,6 +114,8 @@ fn generate_endgame (
 fn generate_winscreen (
 	mut commands: Commands,
 	asset_server: Res<AssetServer>,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
 ) {
 	commands
 		.spawn_bundle(SpriteBundle {
@@ -104,11 +126,15 @@ fn generate_winscreen (
 	.insert(Name::new("Winscreen Sprite"))
 	.insert(EndgameSprites)
 	;
+
+	spawn_endgame_text(&mut commands, &ui_assets, locale.get("endgame.win.title"), locale.get("endgame.win.text"), 476.0);
 }
 
 fn generate_losescreen (
 	mut commands: Commands,
 	asset_server: Res<AssetServer>,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
 ) {
 	commands
 		.spawn_bundle(SpriteBundle {
@@ -119,11 +145,15 @@ fn generate_losescreen (
 	.insert(Name::new("Losescreen Sprite"))
 	.insert(EndgameSprites)
 	;
+
+	spawn_endgame_text(&mut commands, &ui_assets, locale.get("endgame.lose.title"), locale.get("endgame.lose.text"), 552.0);
 }
 
 fn generate_boomscreen (
 	mut commands: Commands,
 	asset_server: Res<AssetServer>,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
 ) {
 	commands
 		.spawn_bundle(SpriteBundle {
@@ -134,6 +164,67 @@ fn generate_boomscreen (
 	.insert(Name::new("Boomscreen Sprite"))
 	.insert(EndgameSprites)
 	;
+
+	spawn_endgame_text(&mut commands, &ui_assets, locale.get("endgame.boom.title"), locale.get("endgame.boom.text"), 456.0);
+}
+
+// Words go over the art as text so they can be translated, the title across the top and the rest
+// left of the picture, as wide as the picture leaves room for
+fn spawn_endgame_text (
+	commands: &mut Commands,
+	ui_assets: &UiAssets,
+	title: &str,
+	text: &str,
+	text_width: f32,
+) {
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				justify_content: JustifyContent::Center,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(0.0, 378.0), Vec2::new(1600.0, 100.0)))
+		.insert(Name::new("Endgame Title Node"))
+		.insert(EndgameSprites)
+		.with_children(|parent| {
+			parent.spawn_bundle(TextBundle::from_section(
+				title,
+				TextStyle {
+					font: ui_assets.font.clone(),
+					font_size: 84.0,
+					color: ENDGAME_TEXT_COLOR,
+				},
+			));
+		});
+
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor { corner: Vec2::new(-712.0, -114.0), size: Vec2::new(text_width, 148.0) })
+		.insert(Name::new("Endgame Text Node"))
+		.insert(EndgameSprites)
+		.with_children(|parent| {
+			parent.spawn_bundle(TextBundle::from_section(
+				text,
+				TextStyle {
+					font: ui_assets.font.clone(),
+					font_size: 36.0,
+					color: ENDGAME_TEXT_COLOR,
+				},
+			));
+		});
 }
 
 fn cut_to_credits (
@@ -148,19 +239,118 @@ fn cut_to_credits (
 	}
 }
 
+// Only the final day of the campaign rolls the credits
+fn cut_to_summary (
+	time: Res<Time>,
+	mut endtime_query: Query<&mut BasicCountdown, Without<LoopTimer>>,
+	campaign_query: Query<&Campaign>,
+	mut state: ResMut<State<GameState>>,
+) {
+	let mut endtime = endtime_query.single_mut();
+	let campaign = campaign_query.single();
+
+	if endtime.0.tick(time.delta()).just_finished() {
+		if campaign.finished {
+			state.push(GameState::Credits).expect("Failed to change states");
+		} else {
+			state.set(GameState::DaySummary).expect("Failed to change states");
+		}
+	}
+}
+
+// Names stay as they are, only the headings and roles are translated
 fn generate_credits (
 	mut commands: Commands,
-	asset_server: Res<AssetServer>,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
+	settings: Res<Settings>,
 ) {
+	let text_style = |font_size: f32| TextStyle {
+		font: ui_assets.font.clone(),
+		font_size,
+		color: ENDGAME_TEXT_COLOR,
+	};
+	let sections: [(&str, Vec<(&str, &str)>); 2] = [
+		(locale.get("credits.dev_team"), vec![
+			("INFINITEFALL", locale.get("credits.programmer_cursed")),
+			("OGELNAC", locale.get("credits.art")),
+			("IQUICK143", locale.get("credits.programmer_physics")),
+		]),
+		(locale.get("credits.special_thanks"), vec![
+			("BEVY", locale.get("credits.bevy")),
+			("LOGICPROJECTS", locale.get("credits.logicprojects")),
+		]),
+	];
+
 	commands
-		.spawn_bundle(SpriteBundle {
-			texture: asset_server.load("endgame/credits.png"),
-            transform: Transform::from_scale(Vec3::new(4.0, 4.0, 0.0)),
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+				flex_direction: FlexDirection::ColumnReverse,
+				justify_content: JustifyContent::SpaceEvenly,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: ENDGAME_BACKGROUND.into(),
 			..default()
 		})
-		.insert(Name::new("Credits Sprite"))
+		.insert(Name::new("Credits UI"))
 		.insert(CreditsSprites)
-		;
+		.with_children(|parent| {
+			parent.spawn_bundle(TextBundle::from_section(
+				locale.get("credits.title"),
+				text_style(84.0),
+			));
+
+			for (heading, rows) in sections {
+				parent
+					.spawn_bundle(NodeBundle {
+						style: Style {
+							size: Size::new(Val::Px(1450.0), Val::Auto),
+							flex_direction: FlexDirection::ColumnReverse,
+							..default()
+						},
+						color: INVISIBLE.into(),
+						..default()
+					})
+					.with_children(|parent| {
+						parent.spawn_bundle(
+							TextBundle::from_section(heading, text_style(40.0))
+							.with_style(Style {
+								margin: UiRect::new(Val::Px(0.0), Val::Px(0.0), Val::Px(0.0), Val::Px(20.0)),
+								..default()
+							}),
+						);
+
+						// Name and role in two columns
+						for (name, role) in rows {
+							parent
+								.spawn_bundle(NodeBundle {
+									color: INVISIBLE.into(),
+									..default()
+								})
+								.with_children(|parent| {
+									parent.spawn_bundle(
+										TextBundle::from_section(name, text_style(36.0))
+										.with_style(Style {
+											size: Size::new(Val::Px(440.0), Val::Auto),
+											..default()
+										}),
+									);
+									parent.spawn_bundle(TextBundle::from_section(
+										format!("- {role}"),
+										text_style(36.0),
+									));
+								});
+						}
+					});
+			}
+
+			parent.spawn_bundle(TextBundle::from_section(
+				locale.format("credits.god_mode", &[("key", &settings.controls.binding(InputAction::ToggleGodMode).label())]),
+				text_style(36.0),
+			));
+		});
 
 	commands
 		.spawn()
@@ -1,4 +1,8 @@
+use bevy::prelude::Color;
 use bevy_inspector_egui::Inspectable;
+use serde::{Serialize, Deserialize};
+
+use crate::locale::Locale;
 
 // Enum for all the different sprite entities you need
 // Maybe convert to a generic object type? idk
@@ -11,7 +15,7 @@ pub enum SpriteType {
 }
 
 // Enum for all the different molecules
-#[derive(Inspectable, Debug, PartialEq, Clone, Copy)]
+#[derive(Inspectable, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum MoleculeType {
 	Red,
 	Blue,
@@ -20,6 +24,15 @@ pub enum MoleculeType {
     Gray,
 }
 
+// Materials the reactor walls can be built from
+#[derive(Inspectable, Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum WallMaterial {
+	Steel,
+	Ceramic,
+	Tungsten,
+	Graphene,
+}
+
 // Check pls
 pub struct Reaction {
 	pub products: Vec<MoleculeType>,
@@ -102,6 +115,17 @@ impl MoleculeType {
 		}
 	}
 
+	// Scales how strongly field emitters push each molecule, sign decides direction
+	pub fn charge(&self) -> f32 {
+		match self {
+			MoleculeType::Red => 1.0,
+			MoleculeType::Blue => -1.0,
+			MoleculeType::Orange => 2.0,
+            MoleculeType::Purple => 0.0,
+            MoleculeType::Gray => -0.5,
+		}
+	}
+
 	pub fn base_cost(&self) -> f32 {
 		match self {
 			MoleculeType::Red => 2.0,
@@ -112,13 +136,88 @@ impl MoleculeType {
 		}
 	}
 
-	pub fn name(&self) -> &'static str {
+	// Named after their symbol instead of their colour while symbols are on
+	pub fn name<'a>(&self, locale: &'a Locale) -> &'a str {
+		locale.get(match (self, locale.symbol_names) {
+			(MoleculeType::Red, false) => "molecule.red",
+			(MoleculeType::Blue, false) => "molecule.blue",
+			(MoleculeType::Orange, false) => "molecule.orange",
+            (MoleculeType::Purple, false) => "molecule.purple",
+            (MoleculeType::Gray, false) => "molecule.gray",
+			(MoleculeType::Red, true) => "molecule.red.shape",
+			(MoleculeType::Blue, true) => "molecule.blue.shape",
+			(MoleculeType::Orange, true) => "molecule.orange.shape",
+            (MoleculeType::Purple, true) => "molecule.purple.shape",
+            (MoleculeType::Gray, true) => "molecule.gray.shape",
+		})
+	}
+
+	pub fn short_name<'a>(&self, locale: &'a Locale) -> &'a str {
+		locale.get(match (self, locale.symbol_names) {
+			(MoleculeType::Red, false) => "molecule.red.short",
+			(MoleculeType::Blue, false) => "molecule.blue.short",
+			(MoleculeType::Orange, false) => "molecule.orange.short",
+            (MoleculeType::Purple, false) => "molecule.purple.short",
+            (MoleculeType::Gray, false) => "molecule.gray.short",
+			(MoleculeType::Red, true) => "molecule.red.shape.short",
+			(MoleculeType::Blue, true) => "molecule.blue.shape.short",
+			(MoleculeType::Orange, true) => "molecule.orange.shape.short",
+            (MoleculeType::Purple, true) => "molecule.purple.shape.short",
+            (MoleculeType::Gray, true) => "molecule.gray.shape.short",
+		})
+	}
+
+	// Drawn over the sprites for players who can't tell the colours apart, all in the pixel font
+	pub fn symbol(&self) -> &'static str {
+		match self {
+			MoleculeType::Red => "+",
+			MoleculeType::Blue => "o",
+			MoleculeType::Orange => "#",
+            MoleculeType::Purple => "*",
+            MoleculeType::Gray => "=",
+		}
+	}
+
+	// Flat colour of the molecule art, palettes are built on top of it
+	pub fn color(&self) -> Color {
+		match self {
+			MoleculeType::Red => Color::rgb(0.9, 0.2, 0.2),
+			MoleculeType::Blue => Color::rgb(0.2, 0.4, 0.9),
+			MoleculeType::Orange => Color::rgb(1.0, 0.6, 0.1),
+            MoleculeType::Purple => Color::rgb(0.6, 0.2, 0.8),
+            MoleculeType::Gray => Color::rgb(0.5, 0.5, 0.5),
+		}
+	}
+}
+
+impl WallMaterial {
+	// Fraction of the velocity into the wall a molecule keeps when it bounces
+	pub fn restitution(&self) -> f32 {
+		match self {
+			WallMaterial::Steel => 1.0,
+			WallMaterial::Ceramic => 0.95,
+			WallMaterial::Tungsten => 0.9,
+			WallMaterial::Graphene => 1.0,
+		}
+	}
+
+	// How far a bouncing molecule's speed is pulled towards the wall's ambient speed [0-1]
+	pub fn conductivity(&self) -> f32 {
+		match self {
+			WallMaterial::Steel => 0.0,
+			WallMaterial::Ceramic => 0.05,
+			WallMaterial::Tungsten => 0.1,
+			WallMaterial::Graphene => 0.2,
+		}
+	}
+
+	// Multiplier on the base max pressure
+	pub fn pressure_tolerance(&self) -> f32 {
 		match self {
-			MoleculeType::Red => "Red Molecule",
-			MoleculeType::Blue => "Blue Molecule",
-			MoleculeType::Orange => "Orange Molecule",
-            MoleculeType::Purple => "Purple Molecule",
-            MoleculeType::Gray => "Gray Molecule"
+			WallMaterial::Steel => 1.0,
+			WallMaterial::Ceramic => 1.3,
+			WallMaterial::Tungsten => 1.6,
+			WallMaterial::Graphene => 2.2,
 		}
 	}
 }
@@ -0,0 +1,299 @@
+use bevy::prelude::*;
+
+use crate::{
+	GameState, INVISIBLE,
+	camera::{PanCamera, cursor_world_position},
+	start_menu::{despawn_screen, UiAssets},
+	reactor::{Dimensions, GodMode},
+	reactor_ui::{Reactor, ReactorUi},
+	economy::{Economy, Power},
+	injector::Injector,
+	research::{Research, ResearchEffect, ReactorPart},
+	layout::WorldAnchor,
+	locale::Locale,
+	controls::{Actions, InputAction},
+	settings::Settings,
+};
+
+// ---------- PLUGINS ----------
+pub struct FieldPlugin;
+
+impl Plugin for FieldPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_system_set(SystemSet::on_enter(GameState::Reactor)
+			.with_system(generate_field_placer)
+		)
+		.add_system_set(SystemSet::on_exit(GameState::Reactor)
+			.with_system(despawn_screen::<FieldEmitter>)
+		)
+		.add_system_set(SystemSet::on_pause(GameState::Reactor)
+			.with_system(despawn_screen::<FieldEmitter>)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(select_field)
+			.with_system(place_field)
+			.with_system(update_field_text)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+const FIELD_COST: f32 = 20.0;
+const MAX_FIELDS: usize = 4;
+const FIELD_RADIUS: f32 = 120.0;
+// How close a right click has to be to an emitter's core to remove it
+pub const EMITTER_PICK_RADIUS: f32 = 16.0;
+// Force in pixels per frame squared on a molecule of charge 1 and mass 1
+const FIELD_STRENGTH: f32 = 0.05;
+
+// ---------- COMPONENTS ----------
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FieldKind {
+	// Constant push in one direction
+	Uniform(Vec2),
+	// Pulls towards the emitter
+	Attractor,
+	// Spins around the emitter
+	Vortex,
+}
+
+impl FieldKind {
+	pub fn name<'a>(&self, locale: &'a Locale) -> &'a str {
+		locale.get(match self {
+			FieldKind::Uniform(_) => "field.uniform",
+			FieldKind::Attractor => "field.attractor",
+			FieldKind::Vortex => "field.vortex",
+		})
+	}
+
+	pub fn color(&self) -> Color {
+		match self {
+			FieldKind::Uniform(_) => Color::rgba(0.2, 0.9, 0.9, 0.15),
+			FieldKind::Attractor => Color::rgba(0.9, 0.9, 0.2, 0.15),
+			FieldKind::Vortex => Color::rgba(0.9, 0.2, 0.9, 0.15),
+		}
+	}
+
+	// Off -> Uniform -> Attractor -> Vortex -> Off
+	fn next(kind: Option<FieldKind>) -> Option<FieldKind> {
+		match kind {
+			None => Some(FieldKind::Uniform(Vec2::X)),
+			Some(FieldKind::Uniform(_)) => Some(FieldKind::Attractor),
+			Some(FieldKind::Attractor) => Some(FieldKind::Vortex),
+			Some(FieldKind::Vortex) => None,
+		}
+	}
+}
+
+#[derive(Component)]
+pub struct FieldEmitter {
+	pub kind: FieldKind,
+	pub strength: f32,
+	pub radius: f32,
+}
+
+impl FieldEmitter {
+	// Force on a unit charge at target, falls off linearly to zero at the edge of the zone
+	pub fn force_at(&self, emitter: Vec2, target: Vec2) -> Vec2 {
+		let offset = emitter - target;
+		let distance = offset.length();
+		if distance > self.radius || distance < 1.0 {
+			return Vec2::ZERO;
+		}
+		let falloff = 1.0 - distance / self.radius;
+		match self.kind {
+			FieldKind::Uniform(direction) => direction * self.strength,
+			FieldKind::Attractor => offset / distance * self.strength * falloff,
+			FieldKind::Vortex => offset.perp() / distance * self.strength * falloff,
+		}
+	}
+}
+
+#[derive(Component)]
+pub struct FieldPlacer {
+	pub armed: Option<FieldKind>,
+	drag_start: Option<Vec2>,
+}
+
+#[derive(Component)]
+struct FieldText;
+
+// ---------- SYSTEMS ----------
+fn generate_field_placer (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
+	settings: Res<Settings>,
+) {
+	commands
+		.spawn()
+		.insert(FieldPlacer {
+			armed: None,
+			drag_start: None,
+		})
+		.insert(Reactor)
+		.insert(Name::new("Field Placer"));
+
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(-190.0, 346.0), Vec2::new(200.0, 24.0)))
+		.insert(Name::new("Field Node"))
+		.insert(ReactorUi)
+		.with_children(|parent| {
+			parent
+				.spawn_bundle(TextBundle::from_section(
+					locale.format("field.off", &[("key", &settings.controls.binding(InputAction::ToggleField).label())]),
+					TextStyle {
+						font: ui_assets.font.clone(),
+						font_size: 16.0,
+						color: Color::WHITE,
+					},
+				))
+				.insert(FieldText);
+		});
+}
+
+fn select_field (
+	actions: Actions,
+	mut placer_query: Query<&mut FieldPlacer>,
+	mut injector_query: Query<&mut Injector>,
+	research_query: Query<&Research>,
+) {
+	let mut placer = placer_query.single_mut();
+	let mut injector = injector_query.single_mut();
+	let research = research_query.single();
+
+	if actions.just_pressed(InputAction::ToggleField) && research.is_unlocked(ResearchEffect::Part(ReactorPart::FieldEmitter)) {
+		placer.armed = FieldKind::next(placer.armed);
+		placer.drag_start = None;
+		// Both tools use the left mouse button
+		injector.armed = None;
+	}
+	// Picking a molecule puts the field away
+	if injector.armed.is_some() {
+		placer.armed = None;
+	}
+}
+
+fn place_field (
+	mut commands: Commands,
+	mouse: Res<Input<MouseButton>>,
+	windows: Res<Windows>,
+	camera_query: Query<(&Camera, &GlobalTransform), With<PanCamera>>,
+	reactor_query: Query<(&Reactor, &Dimensions, &Transform)>,
+	emitter_query: Query<(Entity, &Transform), With<FieldEmitter>>,
+	mut placer_query: Query<&mut FieldPlacer>,
+	mut economy_query: Query<(&Economy, &mut Power)>,
+	godmode_query: Query<&GodMode>,
+) {
+	let mut placer = placer_query.single_mut();
+	let (_reactor, reactor_size, reactor_transform) = reactor_query.single();
+	let (camera, camera_transform) = camera_query.single();
+	let (_economy, mut power) = economy_query.single_mut();
+	let godmode = godmode_query.single();
+
+	let cursor = match cursor_world_position(&windows, camera, camera_transform) {
+		Some(cursor) => cursor,
+		None => return,
+	};
+
+	// Right click removes the emitter under the cursor
+	if mouse.just_pressed(MouseButton::Right) {
+		for (entity, transform) in emitter_query.iter() {
+			if transform.translation.truncate().distance(cursor) <= EMITTER_PICK_RADIUS {
+				commands.entity(entity).despawn_recursive();
+			}
+		}
+		placer.drag_start = None;
+	}
+
+	let kind = match placer.armed {
+		Some(kind) => kind,
+		None => return,
+	};
+
+	if mouse.just_pressed(MouseButton::Left) {
+		let offset = (cursor - reactor_transform.translation.truncate()).abs();
+		if offset.x <= reactor_size.x_size / 2.0 && offset.y <= reactor_size.y_size / 2.0 {
+			placer.drag_start = Some(cursor);
+		}
+	}
+
+	if mouse.just_released(MouseButton::Left) {
+		let drag_start = match placer.drag_start.take() {
+			Some(drag_start) => drag_start,
+			None => return,
+		};
+		if emitter_query.iter().count() >= MAX_FIELDS || !(power.current_power > FIELD_COST || godmode.0) {
+			return;
+		}
+		power.current_power -= FIELD_COST;
+
+		// Uniform fields point along the drag, a short click keeps the default direction
+		let kind = match kind {
+			FieldKind::Uniform(direction) => {
+				let drag = cursor - drag_start;
+				FieldKind::Uniform(if drag.length() > 10.0 {drag.normalize()} else {direction})
+			}
+			kind => kind,
+		};
+
+		commands
+			.spawn_bundle(SpriteBundle {
+				sprite: Sprite {
+					color: kind.color(),
+					custom_size: Some(Vec2::splat(FIELD_RADIUS * 2.0)),
+					..default()
+				},
+				transform: Transform::from_translation(drag_start.extend(850.0)),
+				..default()
+			})
+			.insert(Name::new("Field Emitter"))
+			.insert(FieldEmitter {
+				kind,
+				strength: FIELD_STRENGTH,
+				radius: FIELD_RADIUS,
+			})
+			.with_children(|parent| {
+				let mut core_color = kind.color();
+				core_color.set_a(1.0);
+
+				// Emitter core
+				parent
+					.spawn_bundle(SpriteBundle {
+						sprite: Sprite {
+							color: core_color,
+							custom_size: Some(Vec2::splat(16.0)),
+							..default()
+						},
+						transform: Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
+						..default()
+					});
+			});
+	}
+}
+
+fn update_field_text (
+	placer_query: Query<&FieldPlacer, Changed<FieldPlacer>>,
+	mut text_query: Query<&mut Text, With<FieldText>>,
+	locale: Res<Locale>,
+	settings: Res<Settings>,
+) {
+	for placer in placer_query.iter() {
+		let mut text = text_query.single_mut();
+		let key = settings.controls.binding(InputAction::ToggleField).label();
+		text.sections[0].value = match placer.armed {
+			Some(kind) => locale.format("field.armed", &[("kind", &kind.name(&locale)), ("key", &key)]),
+			None => locale.format("field.off", &[("key", &key)]),
+		};
+	}
+}
@@ -0,0 +1,188 @@
+use bevy::prelude::*;
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::UiAssets,
+	reactor::GodMode,
+	reactor_ui::ReactorUi,
+	economy::{Economy, Power, Clock},
+	layout::WorldAnchor,
+	locale::Locale,
+};
+
+// ---------- PLUGINS ----------
+pub struct GridPlugin;
+
+impl Plugin for GridPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_system_set(SystemSet::on_enter(GameState::Reactor)
+			.with_system(generate_grid)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(deliver_power.label(GridLabel))
+			.with_system(update_grid_text)
+		)
+		;
+	}
+}
+
+// Deliveries settle after the clock moves and before the day is judged, so the last hour counts
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GridLabel;
+
+// ---------- RESOURCES ----------
+// The day runs 9:00 to 17:00
+pub const HOURS: usize = 8;
+// Share of an even hourly split of the target the grid wants each hour, averages out to 1
+// Morning ramp, lunch dip at 12:00, evening peak
+const DEMAND_CURVE: [f32; HOURS] = [0.5, 0.8, 1.1, 0.7, 1.0, 1.1, 1.3, 1.5];
+// Deliveries within this fraction of demand count as meeting it
+const DEMAND_TOLERANCE: f32 = 0.25;
+// Bonus as a fraction of the hour's demand
+const MET_BONUS: f32 = 0.1;
+// Penalties per unit of power short of or over the tolerance band
+const SHORTFALL_PENALTY: f32 = 0.25;
+const OVERPRODUCTION_PENALTY: f32 = 0.1;
+
+// ---------- COMPONENTS ----------
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Delivery {
+	Met,
+	Short,
+	Over,
+}
+
+pub struct HourResult {
+	pub hour: usize,
+	pub demand: f32,
+	pub delivered: f32,
+	pub delivery: Delivery,
+	// Bonus if positive, penalty if negative
+	pub adjustment: f32,
+}
+
+#[derive(Component)]
+pub struct Grid {
+	// Index of the hour being delivered into, HOURS once the day is over
+	pub hour: usize,
+	pub results: Vec<HourResult>,
+}
+
+#[derive(Component)]
+struct GridText;
+
+// ---------- SYSTEMS ----------
+pub fn hourly_demand (
+	hour: usize,
+	target_power: f32,
+) -> f32 {
+	target_power / HOURS as f32 * DEMAND_CURVE[hour.min(HOURS - 1)]
+}
+
+fn generate_grid (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+) {
+	commands
+		.spawn()
+		.insert(Grid {
+			hour: 0,
+			results: Vec::new(),
+		})
+		.insert(Economy)
+		.insert(Name::new("Grid"));
+
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(615.0, -250.0), Vec2::new(300.0, 60.0)))
+		.insert(Name::new("Grid Node"))
+		.insert(ReactorUi)
+		.with_children(|parent| {
+			parent
+				.spawn_bundle(TextBundle::from_section(
+					"",
+					TextStyle {
+						font: ui_assets.font.clone(),
+						font_size: 18.0,
+						color: Color::rgb(0.0, 0.3, 0.0),
+					},
+				))
+				.insert(GridText);
+		});
+}
+
+// Settles each hour against the demand curve when the clock moves past it
+fn deliver_power (
+	mut economy_query: Query<(&Economy, &Clock, &mut Power)>,
+	mut grid_query: Query<&mut Grid>,
+	godmode_query: Query<&GodMode>,
+) {
+	let (_economy, clock, mut power) = economy_query.single_mut();
+	let mut grid = grid_query.single_mut();
+	let godmode = godmode_query.single();
+
+	while grid.hour < clock.hour() {
+		let hour = grid.hour;
+		let demand = hourly_demand(hour, power.target_power);
+		let delivered = power.generated;
+		power.generated = 0.0;
+
+		let (delivery, adjustment) = if delivered < demand * (1.0 - DEMAND_TOLERANCE) {
+			(Delivery::Short, -SHORTFALL_PENALTY * (demand * (1.0 - DEMAND_TOLERANCE) - delivered))
+		} else if delivered > demand * (1.0 + DEMAND_TOLERANCE) {
+			(Delivery::Over, -OVERPRODUCTION_PENALTY * (delivered - demand * (1.0 + DEMAND_TOLERANCE)))
+		} else {
+			(Delivery::Met, MET_BONUS * demand)
+		};
+
+		if !godmode.0 {
+			power.current_power = (power.current_power + adjustment).clamp(0.0, 999000.0);
+		}
+		grid.results.push(HourResult {
+			hour,
+			demand,
+			delivered,
+			delivery,
+			adjustment,
+		});
+		grid.hour += 1;
+	}
+}
+
+fn update_grid_text (
+	economy_query: Query<(&Economy, &Power)>,
+	grid_query: Query<&Grid>,
+	mut text_query: Query<&mut Text, With<GridText>>,
+	locale: Res<Locale>,
+) {
+	let (_economy, power) = economy_query.single();
+	let grid = grid_query.single();
+	let mut text = text_query.single_mut();
+
+	let mut value = locale.format("grid.status", &[
+		("generated", &format!("{:.0}", power.generated)),
+		("demand", &format!("{:.0}", hourly_demand(grid.hour, power.target_power))),
+	]);
+	if let Some(result) = grid.results.last() {
+		let verdict = locale.get(match result.delivery {
+			Delivery::Met => "grid.met",
+			Delivery::Short => "grid.short",
+			Delivery::Over => "grid.over",
+		});
+		value.push('\n');
+		value.push_str(&locale.format("grid.result", &[
+			("hour", &(result.hour + 9)),
+			("verdict", &verdict),
+			("adjustment", &format!("{:+.0}", result.adjustment)),
+		]));
+	}
+	text.sections[0].value = value;
+}
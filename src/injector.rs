@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+
+use crate::{
+	GameState,
+	art::SpriteSheets,
+	camera::{PanCamera, cursor_world_position},
+	reactor::{Dimensions, MoleculeList, Cost, GodMode, spawn_molecule},
+	reactor_ui::Reactor,
+	economy::{Economy, Power, Market},
+	enums::MoleculeType,
+	balance::BalanceConfig,
+};
+
+// ---------- PLUGINS ----------
+pub struct InjectorPlugin;
+
+impl Plugin for InjectorPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_system_set(SystemSet::on_enter(GameState::Reactor)
+			.with_system(generate_injector)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(aim_injection.label(InjectorLabel))
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+// Converts drag length in pixels into launch speed (pixels per frame)
+const LAUNCH_SCALE: f32 = 0.04;
+const MAX_LAUNCH_SPEED: f32 = 8.0;
+// Extra cost per unit of launch speed, as a fraction of the molecule cost
+const LAUNCH_COST_PER_SPEED: f32 = 0.25;
+
+// Other right click handlers need to see the injector before it is put away
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InjectorLabel;
+
+// ---------- COMPONENTS ----------
+#[derive(Component)]
+pub struct Injector {
+	// Molecule picked from the cost buttons, placed by click-dragging in the reactor
+	pub armed: Option<MoleculeType>,
+	drag_start: Option<Vec2>,
+}
+
+#[derive(Component)]
+struct InjectorArrow;
+
+// ---------- SYSTEMS ----------
+fn generate_injector (
+	mut commands: Commands,
+) {
+	commands
+		.spawn()
+		.insert(Injector {
+			armed: None,
+			drag_start: None,
+		})
+		.insert(Reactor)
+		.insert(Name::new("Injector"));
+
+	// Preview arrow showing launch direction and speed while dragging
+	commands
+		.spawn_bundle(SpriteBundle {
+			sprite: Sprite {
+				color: Color::rgba(1.0, 1.0, 1.0, 0.8),
+				custom_size: Some(Vec2::new(0.0, 6.0)),
+				..default()
+			},
+			visibility: Visibility { is_visible: false },
+			transform: Transform::from_translation(Vec3::new(0.0, 0.0, 960.0)),
+			..default()
+		})
+		.insert(InjectorArrow)
+		.insert(Reactor)
+		.insert(Name::new("Injector Arrow"));
+}
+
+pub fn launch_cost (
+	molecule_cost: f32,
+	velocity: Vec2,
+) -> f32 {
+	molecule_cost * (1.0 + velocity.length() * LAUNCH_COST_PER_SPEED)
+}
+
+fn aim_injection (
+	mut commands: Commands,
+	spritesheet: Res<SpriteSheets>,
+	mouse: Res<Input<MouseButton>>,
+	windows: Res<Windows>,
+	camera_query: Query<(&Camera, &GlobalTransform), With<PanCamera>>,
+	reactor_query: Query<(&Reactor, &Dimensions, &Transform), Without<InjectorArrow>>,
+	mut injector_query: Query<&mut Injector>,
+	mut arrow_query: Query<(&mut Transform, &mut Sprite, &mut Visibility), With<InjectorArrow>>,
+	mut molecule_list_query: Query<&MoleculeList>,
+	cost_query: Query<&Cost>,
+	mut market_query: Query<&mut Market>,
+	mut economy_query: Query<(&Economy, &mut Power)>,
+	godmode_query: Query<&GodMode>,
+	balance: Res<BalanceConfig>,
+) {
+	let mut injector = injector_query.single_mut();
+	let (mut arrow_transform, mut arrow_sprite, mut arrow_visibility) = arrow_query.single_mut();
+	let (_reactor, reactor_size, reactor_transform) = reactor_query.single();
+	let (camera, camera_transform) = camera_query.single();
+	let (_economy, mut power) = economy_query.single_mut();
+	let cost = cost_query.single();
+	let mut market = market_query.single_mut();
+	let godmode = godmode_query.single();
+
+	// Right click puts the molecule back
+	if mouse.just_pressed(MouseButton::Right) {
+		injector.armed = None;
+		injector.drag_start = None;
+	}
+
+	let molecule_type = match injector.armed {
+		Some(molecule_type) => molecule_type,
+		None => {
+			arrow_visibility.is_visible = false;
+			return;
+		}
+	};
+
+	let cursor = match cursor_world_position(&windows, camera, camera_transform) {
+		Some(cursor) => cursor,
+		None => return,
+	};
+
+	if mouse.just_pressed(MouseButton::Left) {
+		let offset = (cursor - reactor_transform.translation.truncate()).abs();
+		// Only start a drag inside the reactor so clicking the cost buttons doesn't launch anything
+		if offset.x <= (reactor_size.x_size - 32.0) / 2.0 && offset.y <= (reactor_size.y_size - 32.0) / 2.0 {
+			injector.drag_start = Some(cursor);
+		}
+	}
+
+	let drag_start = match injector.drag_start {
+		Some(drag_start) => drag_start,
+		None => return,
+	};
+
+	let velocity = ((cursor - drag_start) * LAUNCH_SCALE).clamp_length_max(MAX_LAUNCH_SPEED);
+	let molecule_cost = launch_cost(*cost.get_cost(molecule_type), velocity);
+	let affordable = power.current_power > molecule_cost || godmode.0;
+
+	if mouse.pressed(MouseButton::Left) {
+		// Arrow length shows the clamped launch speed rather than the raw drag
+		let arrow = velocity / LAUNCH_SCALE;
+		arrow_visibility.is_visible = true;
+		arrow_transform.translation = (drag_start + arrow / 2.0).extend(960.0);
+		arrow_transform.rotation = Quat::from_rotation_z(arrow.y.atan2(arrow.x));
+		arrow_sprite.custom_size = Some(Vec2::new(arrow.length(), 6.0));
+		arrow_sprite.color = if affordable {Color::rgba(1.0, 1.0, 1.0, 0.8)} else {Color::rgba(1.0, 0.2, 0.2, 0.8)};
+	}
+
+	if mouse.just_released(MouseButton::Left) {
+		arrow_visibility.is_visible = false;
+		injector.drag_start = None;
+		if affordable {
+			spawn_molecule(molecule_type, &mut commands, &spritesheet, &mut molecule_list_query, 0, drag_start.extend(900.0), velocity);
+			power.current_power -= molecule_cost;
+			market.record_purchase(molecule_type, balance.cost_scaling);
+		}
+	}
+}
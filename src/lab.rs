@@ -1,12 +1,14 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::FocusPolicy};
 
 use crate::art::{spawn_spritesheet_sprite, SpriteSheets};
 use crate::economy::Economy;
 use crate::enums::SpriteType;
 use crate::reactor::{generate_molecule_list, MoleculeList};
 use crate::{GameState, INVISIBLE};
+use crate::layout::WorldAnchor;
+use crate::locale::Locale;
 
-use crate::start_menu::despawn_screen;
+use crate::start_menu::{despawn_screen, UiAssets};
 
 // ---------- PLUGINS ----------
 pub struct LabPlugin;
@@ -28,6 +30,8 @@ impl Plugin for LabPlugin {
 		.with_system(update_lab_sprite)
 			.with_system(button_reactor)
 			.with_system(button_logbook)
+			.with_system(button_shop)
+			.with_system(button_research)
 			.with_system(button_exit)
 		)
 		.add_system_set(SystemSet::on_pause(GameState::Lab)
@@ -63,6 +67,12 @@ struct ReactorButton;
 #[derive(Component)]
 struct LogbookButton;
 
+#[derive(Component)]
+struct ShopButton;
+
+#[derive(Component)]
+struct ResearchButton;
+
 #[derive(Component)]
 struct ExitButton;
 
@@ -74,6 +84,8 @@ fn load_lab (
 	mut commands: Commands,
 	spritesheet: Res<SpriteSheets>,
 	asset_server: Res<AssetServer>,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
 ) {   
 	let lab = spawn_spritesheet_sprite(
 		&mut commands,
@@ -107,15 +119,6 @@ fn load_lab (
 		.spawn_bundle(NodeBundle {
 			style: Style {
 				position_type: PositionType::Absolute,
-				position: UiRect {
-					// Screen width, sprite width, x translation
-					left: Val::Px(1600.0 / 2.0 - 230.0 / 2.0 + 231.0),
-					// Screen height, sprite height, y translation
-					bottom: Val::Px(900.0 / 2.0 - 100.0 / 2.0 - 8.8), 
-					..default()
-				},
-				// Sprite width and height
-				size: Size::new(Val::Px(230.0), Val::Px(100.0)),
 				..default()
 			},
 			color: INVISIBLE.into(),
@@ -123,6 +126,7 @@ fn load_lab (
 			transform: Transform::from_rotation(Quat::from_rotation_z(-0.12)),
 			..default()
 		})
+		.insert(WorldAnchor::new(Vec2::new(231.0, -8.8), Vec2::new(230.0, 100.0)))
 		.insert(Name::new("Reactor Node"))
 		.insert(LabMenuUi)
 		.with_children(|parent| {
@@ -144,15 +148,6 @@ fn load_lab (
 		.spawn_bundle(NodeBundle {
 			style: Style {
 				position_type: PositionType::Absolute,
-				position: UiRect {
-					// Screen width, sprite width, x translation
-					left: Val::Px(1600.0 / 2.0 - 320.0 / 2.0 - 290.8),
-					// Screen height, sprite height, y translation
-					bottom: Val::Px(900.0 / 2.0 - 120.0 / 2.0 - 174.5), 
-					..default()
-				},
-				// Sprite width and height
-				size: Size::new(Val::Px(320.0), Val::Px(120.0)),
 				..default()
 			},
 			color: INVISIBLE.into(),
@@ -160,6 +155,7 @@ fn load_lab (
 			transform: Transform::from_rotation(Quat::from_rotation_z(0.2)),
 			..default()
 		})
+		.insert(WorldAnchor::new(Vec2::new(-290.8, -174.5), Vec2::new(320.0, 120.0)))
 		.insert(Name::new("Logbook Node"))
 		.insert(LabMenuUi)
 		.with_children(|parent| {
@@ -181,15 +177,6 @@ fn load_lab (
 		.spawn_bundle(NodeBundle {
 			style: Style {
 				position_type: PositionType::Absolute,
-				position: UiRect {
-					// Screen width, sprite width, x translation
-					left: Val::Px(1600.0 / 2.0 - 320.0 / 2.0 - 750.3),
-					// Screen height, sprite height, y translation
-					bottom: Val::Px(900.0 / 2.0 - 650.0 / 2.0 + 26.2), 
-					..default()
-				},
-				// Sprite width and height
-				size: Size::new(Val::Px(320.0), Val::Px(650.0)),
 				..default()
 			},
 			color: INVISIBLE.into(),
@@ -197,6 +184,7 @@ fn load_lab (
 			transform: Transform::from_rotation(Quat::from_rotation_z(0.41)),
 			..default()
 		})
+		.insert(WorldAnchor::new(Vec2::new(-750.3, 26.2), Vec2::new(320.0, 650.0)))
 		.insert(Name::new("Exit Node"))
 		.insert(LabMenuUi)
 		.with_children(|parent| {
@@ -214,6 +202,112 @@ fn load_lab (
 				.insert(ExitButton);
 		});
 
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(650.0, -380.0), Vec2::new(200.0, 60.0)))
+		.insert(Name::new("Shop Node"))
+		.insert(LabMenuUi)
+		.with_children(|parent| {
+
+			// Shop Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: Style {
+						size: Size::new(Val::Px(200.0), Val::Px(60.0)),
+						..default()
+					},
+					color: ui_assets.button_color.into(),
+					..default()
+				})
+				.insert(ShopButton)
+				.with_children(|parent| {
+
+					// Shop Button Sprite
+					parent
+						.spawn_bundle(ImageBundle {
+							style: Style {
+								size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+								justify_content: JustifyContent::Center,
+								align_items: AlignItems::Center,
+								..Default::default()
+							},
+						image: ui_assets.button_idle.clone().into(),
+						..Default::default()
+					})
+					.insert(FocusPolicy::Pass)
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							locale.get("lab.shop"),
+							TextStyle {
+								font: ui_assets.font.clone(),
+								font_size: 40.0,
+								color: ui_assets.text_color,
+							},
+						));
+					});
+				});
+		});
+
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(650.0, -300.0), Vec2::new(200.0, 60.0)))
+		.insert(Name::new("Research Node"))
+		.insert(LabMenuUi)
+		.with_children(|parent| {
+
+			// Research Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: Style {
+						size: Size::new(Val::Px(200.0), Val::Px(60.0)),
+						..default()
+					},
+					color: ui_assets.button_color.into(),
+					..default()
+				})
+				.insert(ResearchButton)
+				.with_children(|parent| {
+
+					// Research Button Sprite
+					parent
+						.spawn_bundle(ImageBundle {
+							style: Style {
+								size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+								justify_content: JustifyContent::Center,
+								align_items: AlignItems::Center,
+								..Default::default()
+							},
+						image: ui_assets.button_idle.clone().into(),
+						..Default::default()
+					})
+					.insert(FocusPolicy::Pass)
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							locale.get("lab.research"),
+							TextStyle {
+								font: ui_assets.font.clone(),
+								font_size: 40.0,
+								color: ui_assets.text_color,
+							},
+						));
+					});
+				});
+		});
+
 }
 
 fn update_lab_sprite (
@@ -268,6 +362,54 @@ fn button_logbook (
 	}
 }
 
+fn button_shop (
+	mut interaction_query: Query<(&ShopButton, &Children, &Interaction), Changed<Interaction>>,
+	mut image_query: Query<&mut UiImage>,
+	ui_assets: Res<UiAssets>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, children, interaction) in interaction_query.iter_mut() {
+		let child = children.iter().next().unwrap();
+		let mut image = image_query.get_mut(*child).unwrap();
+
+		match interaction {
+			Interaction::Clicked => {
+				state.push(GameState::Shop).expect("Failed to change states");
+			}
+			Interaction::Hovered => {
+				image.0 = ui_assets.button_hovered.clone();
+			}
+			Interaction::None => {
+				image.0 = ui_assets.button_idle.clone();
+			}
+		}
+	}
+}
+
+fn button_research (
+	mut interaction_query: Query<(&ResearchButton, &Children, &Interaction), Changed<Interaction>>,
+	mut image_query: Query<&mut UiImage>,
+	ui_assets: Res<UiAssets>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, children, interaction) in interaction_query.iter_mut() {
+		let child = children.iter().next().unwrap();
+		let mut image = image_query.get_mut(*child).unwrap();
+
+		match interaction {
+			Interaction::Clicked => {
+				state.push(GameState::Research).expect("Failed to change states");
+			}
+			Interaction::Hovered => {
+				image.0 = ui_assets.button_hovered.clone();
+			}
+			Interaction::None => {
+				image.0 = ui_assets.button_idle.clone();
+			}
+		}
+	}
+}
+
 fn button_exit (
 	mut lab_query: Query<&mut LabSpriteIndex>,
 	mut interaction_query: Query<(&ExitButton, &Interaction), Changed<Interaction>>,
@@ -0,0 +1,186 @@
+use bevy::{prelude::*, ui::UiSystem};
+
+use crate::ASPECT_RATIO;
+
+// ---------- PLUGINS ----------
+pub struct LayoutPlugin;
+
+impl Plugin for LayoutPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.insert_resource(LayoutScale {
+			scale: 1.0,
+			window_size: Vec2::new(WORLD_HEIGHT * ASPECT_RATIO, WORLD_HEIGHT),
+		})
+		// Runs after commands from Update are applied and before bevy_ui lays the nodes out
+		.add_system_to_stage(CoreStage::PostUpdate, update_layout_scale.before(UiSystem::Flex))
+		.add_system_to_stage(CoreStage::PostUpdate, rescale_nodes.after(update_layout_scale).before(UiSystem::Flex))
+		.add_system_to_stage(CoreStage::PostUpdate, scale_new_nodes.after(rescale_nodes).before(UiSystem::Flex))
+		.add_system_to_stage(CoreStage::PostUpdate, place_anchored_nodes.after(update_layout_scale).before(UiSystem::Flex))
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+// World units the camera always shows vertically, the art is laid out for 1600x900
+pub const WORLD_HEIGHT: f32 = 900.0;
+
+// Pixels per world unit, and the window they were measured on
+#[derive(PartialEq)]
+pub struct LayoutScale {
+	pub scale: f32,
+	pub window_size: Vec2,
+}
+
+impl LayoutScale {
+	// Window position, from the bottom left, of a point in the world
+	pub fn to_window(&self, world: Vec2) -> Vec2 {
+		self.window_size / 2.0 + world * self.scale
+	}
+}
+
+// ---------- COMPONENTS ----------
+// Places an absolute UI node over the world like a sprite, so it stays on its art at any window size
+// Full-screen menus don't need this, flexbox keeps them centred and their pixel sizes scale like any other node
+#[derive(Component, Clone, Copy)]
+pub struct WorldAnchor {
+	// World position of the node's bottom left corner
+	pub corner: Vec2,
+	// Node size in world units
+	pub size: Vec2,
+}
+
+impl WorldAnchor {
+	// Covers a sprite of this size at this translation
+	pub fn new(translation: Vec2, sprite_size: Vec2) -> Self {
+		Self {
+			corner: translation - sprite_size / 2.0,
+			size: sprite_size,
+		}
+	}
+
+	// Keeps the sprite's corner but gives the node its own size
+	pub fn with_size(mut self, size: Vec2) -> Self {
+		self.size = size;
+		self
+	}
+}
+
+// Pixel sizes a node was spawned with for the 1600x900 layout, scaled from whenever the window changes
+#[derive(Component)]
+struct BaseLayout {
+	style: Style,
+	font_sizes: Vec<f32>,
+}
+
+// ---------- SYSTEMS ----------
+fn update_layout_scale (
+	windows: Res<Windows>,
+	mut layout: ResMut<LayoutScale>,
+) {
+	if let Some(window) = windows.get_primary() {
+		let window_size = Vec2::new(window.width(), window.height());
+		let new_layout = LayoutScale {
+			scale: window_size.y / WORLD_HEIGHT,
+			window_size,
+		};
+		// Only touch it on a real change so the other systems can rely on change detection
+		if *layout != new_layout {
+			*layout = new_layout;
+		}
+	}
+}
+
+fn place_anchored_nodes (
+	layout: Res<LayoutScale>,
+	mut anchor_query: Query<(ChangeTrackers<WorldAnchor>, &WorldAnchor, &mut Style)>,
+) {
+	for (tracker, anchor, mut style) in anchor_query.iter_mut() {
+		if !layout.is_changed() && !tracker.is_changed() {
+			continue;
+		}
+		let corner = layout.to_window(anchor.corner);
+		style.position_type = PositionType::Absolute;
+		style.position.left = Val::Px(corner.x);
+		style.position.bottom = Val::Px(corner.y);
+		style.size = Size::new(Val::Px(anchor.size.x * layout.scale), Val::Px(anchor.size.y * layout.scale));
+	}
+}
+
+// Remembers the spawned sizes of every UI node and scales it straight away, anchored nodes are sized by their anchor
+fn scale_new_nodes (
+	mut commands: Commands,
+	layout: Res<LayoutScale>,
+	mut node_query: Query<(Entity, &mut Style, Option<&mut Text>), (Added<Node>, Without<WorldAnchor>)>,
+) {
+	for (entity, mut style, text) in node_query.iter_mut() {
+		let base = BaseLayout {
+			style: style.clone(),
+			font_sizes: text.as_ref().map(|text| text.sections.iter().map(|section| section.style.font_size).collect()).unwrap_or_default(),
+		};
+		apply_scale(&base, layout.scale, &mut style, text);
+		commands.entity(entity).insert(base);
+	}
+}
+
+fn rescale_nodes (
+	layout: Res<LayoutScale>,
+	mut node_query: Query<(&BaseLayout, &mut Style, Option<&mut Text>)>,
+) {
+	if !layout.is_changed() {
+		return;
+	}
+	for (base, mut style, text) in node_query.iter_mut() {
+		apply_scale(base, layout.scale, &mut style, text);
+	}
+}
+
+fn apply_scale (
+	base: &BaseLayout,
+	scale: f32,
+	style: &mut Style,
+	text: Option<Mut<Text>>,
+) {
+	style.size = scale_size(base.style.size, scale);
+	style.min_size = scale_size(base.style.min_size, scale);
+	style.max_size = scale_size(base.style.max_size, scale);
+	style.position = scale_rect(base.style.position, scale);
+	style.margin = scale_rect(base.style.margin, scale);
+	style.padding = scale_rect(base.style.padding, scale);
+	style.border = scale_rect(base.style.border, scale);
+	if let Some(mut text) = text {
+		for (section, font_size) in text.sections.iter_mut().zip(&base.font_sizes) {
+			section.style.font_size = font_size * scale;
+		}
+	}
+}
+
+// Percentages and auto already follow the parent, only pixels need scaling
+fn scale_val (
+	val: Val,
+	scale: f32,
+) -> Val {
+	match val {
+		Val::Px(pixels) => Val::Px(pixels * scale),
+		other => other,
+	}
+}
+
+fn scale_size (
+	size: Size<Val>,
+	scale: f32,
+) -> Size<Val> {
+	Size::new(scale_val(size.width, scale), scale_val(size.height, scale))
+}
+
+fn scale_rect (
+	rect: UiRect<Val>,
+	scale: f32,
+) -> UiRect<Val> {
+	UiRect {
+		left: scale_val(rect.left, scale),
+		right: scale_val(rect.right, scale),
+		top: scale_val(rect.top, scale),
+		bottom: scale_val(rect.bottom, scale),
+	}
+}
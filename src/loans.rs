@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::UiAssets,
+	reactor_ui::ReactorUi,
+	economy::{Economy, Power, Clock},
+	battery::BatteryLabel,
+	grid::GridLabel,
+	layout::WorldAnchor,
+	locale::Locale,
+	controls::{Actions, InputAction},
+	settings::Settings,
+};
+
+// ---------- PLUGINS ----------
+pub struct LoanPlugin;
+
+impl Plugin for LoanPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_system_set(SystemSet::on_enter(GameState::Reactor)
+			.with_system(generate_debt)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			// Between the battery feeding the grid and the grid resetting its hourly count
+			.with_system(repay_debt.after(BatteryLabel).before(GridLabel))
+			.with_system(take_loan)
+			.with_system(update_debt_text)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+const LOAN_AMOUNT: f32 = 200.0;
+// Charged up front on every loan
+const LOAN_FEE: f32 = 0.1;
+// Charged on the outstanding balance every in-game hour
+const HOURLY_INTEREST: f32 = 0.05;
+const MAX_DEBT: f32 = 1000.0;
+// Share of generated power that goes to paying the debt off
+const REPAYMENT_SHARE: f32 = 0.5;
+
+// ---------- COMPONENTS ----------
+// Cleared at the end of every day, whatever is left counts against the target
+#[derive(Component)]
+pub struct Debt {
+	pub balance: f32,
+	// Hour interest was last charged for
+	pub hour: usize,
+	// Power.generated as of the last repayment
+	pub seen_generated: f32,
+}
+
+#[derive(Component)]
+struct LoanButton;
+
+#[derive(Component)]
+struct DebtText;
+
+// ---------- SYSTEMS ----------
+fn generate_debt (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+) {
+	commands
+		.spawn()
+		.insert(Debt {
+			balance: 0.0,
+			hour: 0,
+			seen_generated: 0.0,
+		})
+		.insert(Economy)
+		.insert(Name::new("Debt"));
+
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(470.0, 390.0), Vec2::new(160.0, 48.0)))
+		.insert(Name::new("Loan Node"))
+		.insert(ReactorUi)
+		.with_children(|parent| {
+
+			// Loan Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: Style {
+						size: Size::new(Val::Px(160.0), Val::Px(48.0)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					color: INVISIBLE.into(),
+					..default()
+				})
+				.insert(LoanButton)
+				.with_children(|parent| {
+					parent
+						.spawn_bundle(TextBundle::from_section(
+							"",
+							TextStyle {
+								font: ui_assets.font.clone(),
+								font_size: 20.0,
+								color: ui_assets.text_color,
+							},
+						))
+						.insert(DebtText);
+				});
+		});
+}
+
+fn take_loan (
+	actions: Actions,
+	interaction_query: Query<&Interaction, (Changed<Interaction>, With<LoanButton>)>,
+	mut debt_query: Query<&mut Debt>,
+	mut economy_query: Query<(&Economy, &mut Power)>,
+) {
+	let clicked = interaction_query.iter().any(|interaction| *interaction == Interaction::Clicked);
+	if !clicked && !actions.just_pressed(InputAction::TakeLoan) {
+		return;
+	}
+
+	let mut debt = debt_query.single_mut();
+	let (_economy, mut power) = economy_query.single_mut();
+	let owed = LOAN_AMOUNT * (1.0 + LOAN_FEE);
+	if debt.balance + owed <= MAX_DEBT {
+		debt.balance += owed;
+		power.current_power = (power.current_power + LOAN_AMOUNT).clamp(0.0, 999000.0);
+	}
+}
+
+// Takes a share of freshly generated power and charges hourly interest
+fn repay_debt (
+	mut debt_query: Query<&mut Debt>,
+	mut economy_query: Query<(&Economy, &Clock, &mut Power)>,
+) {
+	let mut debt = debt_query.single_mut();
+	let (_economy, clock, mut power) = economy_query.single_mut();
+
+	while debt.hour < clock.hour() {
+		debt.hour += 1;
+		debt.balance *= 1.0 + HOURLY_INTEREST;
+	}
+
+	// The grid zeroes its count every hour
+	if power.generated < debt.seen_generated {
+		debt.seen_generated = 0.0;
+	}
+	let fresh = power.generated - debt.seen_generated;
+	debt.seen_generated = power.generated;
+
+	let repayment = (fresh * REPAYMENT_SHARE).min(debt.balance).min(power.current_power).max(0.0);
+	debt.balance -= repayment;
+	power.current_power -= repayment;
+}
+
+fn update_debt_text (
+	debt_query: Query<&Debt>,
+	mut text_query: Query<&mut Text, With<DebtText>>,
+	locale: Res<Locale>,
+	settings: Res<Settings>,
+) {
+	let debt = debt_query.single();
+	let mut text = text_query.single_mut();
+
+	let key = settings.controls.binding(InputAction::TakeLoan).label();
+	text.sections[0].value = if debt.balance > 0.0 {
+		locale.format("loans.debt", &[("amount", &format!("{:.0}", debt.balance)), ("key", &key)])
+	} else {
+		locale.format("loans.loan", &[("amount", &format!("{LOAN_AMOUNT:.0}")), ("key", &key)])
+	};
+}
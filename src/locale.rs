@@ -0,0 +1,386 @@
+use std::{collections::HashMap, fmt::Display, fs, path::PathBuf};
+
+use ab_glyph::Font as _;
+use bevy::{prelude::*, asset::{FileAssetIo, LoadState}, ui::FocusPolicy};
+use serde::Deserialize;
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::{UiAssets, UiButton, OPTION_COLOR, SELECTED_OPTION_COLOR, despawn_screen, show_buttons},
+	settings::Settings,
+};
+
+// ---------- PLUGINS ----------
+pub struct LocalePlugin;
+
+impl Plugin for LocalePlugin {
+	fn build(&self, app: &mut App) {
+		// Menus are built on the first frame, so the strings have to be there before any asset loads
+		let locale = Locale::load(app.world.resource::<Settings>());
+		app
+		.insert_resource(locale)
+		.insert_resource(LanguageMenu {
+			dirty: false,
+		})
+		.add_startup_system(load_locale_fonts)
+		.add_system(change_language)
+		.add_system(select_font.after(change_language))
+		// LANGUAGE MENU
+		.add_system_set(SystemSet::on_enter(GameState::SettingsLanguageMenu)
+			.with_system(open_language_menu)
+		)
+		.add_system_set(SystemSet::on_update(GameState::SettingsLanguageMenu)
+			.with_system(show_buttons)
+			.with_system(button_language)
+			.with_system(button_language_back)
+			.with_system(load_language_menu.after(button_language).after(change_language))
+		)
+		.add_system_set(SystemSet::on_exit(GameState::SettingsLanguageMenu)
+			.with_system(despawn_screen::<LanguageMenuUi>)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+// Every other language is checked against this one and falls back to it
+pub const DEFAULT_LANGUAGE: &str = "en";
+const LOCALE_DIR: &str = "locale";
+// Wider coverage than the pixel font, for languages it can't draw
+const FALLBACK_FONT: &str = "fonts/DejaVuSans-Bold.ttf";
+
+// One file per language in assets/locale, named by its language code
+#[derive(Deserialize, Default)]
+struct LocaleFile {
+	// Shown in the language picker, in the language itself
+	name: String,
+	strings: HashMap<String, String>,
+}
+
+pub struct Locale {
+	pub language: String,
+	// Molecules go by their symbol instead of their colour, see accessibility.rs
+	pub symbol_names: bool,
+	strings: HashMap<String, String>,
+	fallback: HashMap<String, String>,
+}
+
+impl Locale {
+	pub fn load(settings: &Settings) -> Self {
+		let language = settings.language.as_str();
+		let fallback = read_locale_file(DEFAULT_LANGUAGE).unwrap_or_default().strings;
+		let strings = if language == DEFAULT_LANGUAGE {
+			HashMap::new()
+		} else {
+			read_locale_file(language).unwrap_or_default().strings
+		};
+		Self {
+			language: language.to_string(),
+			symbol_names: settings.accessibility.symbols,
+			strings,
+			fallback,
+		}
+	}
+
+	// Untranslated strings come out in English, unknown keys as the key itself so they stand out
+	pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+		self.strings.get(key)
+			.or_else(|| self.fallback.get(key))
+			.map(String::as_str)
+			.unwrap_or(key)
+	}
+
+	// Fills in {name} placeholders, translations are free to move them around
+	pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+		let mut text = self.get(key).to_string();
+		for (name, value) in args {
+			text = text.replace(&format!("{{{name}}}"), &value.to_string());
+		}
+		text
+	}
+
+	fn characters(&self) -> impl Iterator<Item = char> + '_ {
+		self.strings.values()
+			.chain(self.fallback.values())
+			.flat_map(|text| text.chars())
+			.filter(|character| !character.is_whitespace())
+	}
+}
+
+struct LocaleFonts {
+	primary: Handle<Font>,
+	fallback: Handle<Font>,
+	// Language the current font was picked for
+	checked: Option<String>,
+}
+
+// Set while the language menu needs rebuilding
+struct LanguageMenu {
+	dirty: bool,
+}
+
+// ---------- COMPONENTS ----------
+#[derive(Component)]
+struct LanguageMenuUi;
+
+#[derive(Component)]
+struct LanguageButton(String);
+
+#[derive(Component)]
+struct LanguageBackButton;
+
+// ---------- SYSTEMS ----------
+// Same folder the AssetServer reads from
+fn assets_dir () -> PathBuf {
+	FileAssetIo::get_root_path().join("assets")
+}
+
+fn read_locale_file (
+	language: &str,
+) -> Option<LocaleFile> {
+	let path = assets_dir().join(LOCALE_DIR).join(format!("{language}.ron"));
+	let text = fs::read_to_string(&path)
+		.map_err(|error| warn!("Failed to read {}: {error}", path.display()))
+		.ok()?;
+	ron::from_str(&text)
+		.map_err(|error| warn!("Failed to parse {}: {error}", path.display()))
+		.ok()
+}
+
+// Language codes and names of every string file, default language first
+pub fn available_languages () -> Vec<(String, String)> {
+	let mut codes: Vec<String> = fs::read_dir(assets_dir().join(LOCALE_DIR))
+		.map(|entries| entries
+			.filter_map(|entry| entry.ok())
+			.map(|entry| entry.path())
+			.filter(|path| path.extension().map_or(false, |extension| extension == "ron"))
+			.filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+			.collect())
+		.unwrap_or_default();
+	codes.sort_by_key(|code| (code != DEFAULT_LANGUAGE, code.clone()));
+	codes.into_iter()
+		.filter_map(|code| read_locale_file(&code).map(|file| (code, file.name)))
+		.collect()
+}
+
+fn load_locale_fonts (
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+	ui_assets: Res<UiAssets>,
+) {
+	commands.insert_resource(LocaleFonts {
+		primary: ui_assets.font.clone(),
+		fallback: asset_server.load(FALLBACK_FONT),
+		checked: None,
+	});
+}
+
+// Molecule names follow the accessibility symbols, so those reload the strings too
+fn change_language (
+	settings: Res<Settings>,
+	mut locale: ResMut<Locale>,
+	mut language_menu: ResMut<LanguageMenu>,
+) {
+	if settings.is_changed() && (settings.language != locale.language || settings.accessibility.symbols != locale.symbol_names) {
+		*locale = Locale::load(&settings);
+		language_menu.dirty = true;
+	}
+}
+
+// Swaps to the fallback font when the pixel font lacks any glyph the language uses
+fn select_font (
+	locale: Res<Locale>,
+	asset_server: Res<AssetServer>,
+	fonts: Res<Assets<Font>>,
+	mut locale_fonts: ResMut<LocaleFonts>,
+	mut ui_assets: ResMut<UiAssets>,
+	mut text_query: Query<&mut Text>,
+) {
+	if locale_fonts.checked.as_ref() == Some(&locale.language) {
+		return;
+	}
+	// Checked again next frame once it has loaded
+	let primary = match fonts.get(&locale_fonts.primary) {
+		Some(font) => font,
+		None => return,
+	};
+	let covered = locale.characters().all(|character| primary.font.glyph_id(character).0 != 0);
+	// Only swap to a font that has actually loaded, the pixel font's gaps beat no text at all
+	let font = if covered {
+		locale_fonts.primary.clone()
+	} else if fonts.get(&locale_fonts.fallback).is_some() {
+		locale_fonts.fallback.clone()
+	} else if asset_server.get_load_state(&locale_fonts.fallback) == LoadState::Failed {
+		warn!("Fallback font {FALLBACK_FONT} failed to load, some {} text may be missing glyphs", locale.language);
+		locale_fonts.primary.clone()
+	} else {
+		return;
+	};
+	locale_fonts.checked = Some(locale.language.clone());
+	if font == ui_assets.font {
+		return;
+	}
+
+	// Text already on screen changes over too
+	for mut text in text_query.iter_mut() {
+		for section in text.sections.iter_mut() {
+			if section.style.font == ui_assets.font {
+				section.style.font = font.clone();
+			}
+		}
+	}
+	ui_assets.font = font;
+}
+
+fn open_language_menu (
+	mut language_menu: ResMut<LanguageMenu>,
+) {
+	language_menu.dirty = true;
+}
+
+// Rebuilt whenever the language changes so the menu itself is translated straight away
+fn load_language_menu (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
+	mut language_menu: ResMut<LanguageMenu>,
+	ui_query: Query<Entity, With<LanguageMenuUi>>,
+) {
+	if !language_menu.dirty {
+		return;
+	}
+	language_menu.dirty = false;
+	for entity in ui_query.iter() {
+		commands.entity(entity).despawn_recursive();
+	}
+
+	let text_style = TextStyle {
+		font: ui_assets.font.clone(),
+		font_size: 32.0,
+		color: ui_assets.text_color,
+	};
+
+	commands
+		// Node Bundle to hold the Language Menu UI elements
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				margin: UiRect::all(Val::Auto),
+				flex_direction: FlexDirection::ColumnReverse,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(LanguageMenuUi)
+		.insert(Name::new("Language Menu UI"))
+		.with_children(|parent| {
+
+			// Language Buttons, highlighted while it's the current language
+			for (code, name) in available_languages() {
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: Style {
+							size: Size::new(Val::Px(300.0), Val::Px(50.0)),
+							margin: UiRect::all(Val::Px(5.0)),
+							justify_content: JustifyContent::Center,
+							align_items: AlignItems::Center,
+							..default()
+						},
+						color: if code == locale.language {SELECTED_OPTION_COLOR.into()} else {OPTION_COLOR.into()},
+						..default()
+					})
+					.insert(LanguageButton(code))
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							name,
+							text_style.clone(),
+						));
+					});
+			}
+
+			// Back Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: Style {
+						size: Size::new(Val::Px(250.0), Val::Px(65.0)),
+						margin: UiRect::all(Val::Px(20.0)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					color: ui_assets.button_color.into(),
+					..default()
+				})
+				.insert(LanguageBackButton)
+				.insert(UiButton)
+				.with_children(|parent| {
+
+					// Back Button Sprite
+					parent
+						.spawn_bundle(ImageBundle {
+							style: Style {
+								size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+								justify_content: JustifyContent::Center,
+								align_items: AlignItems::Center,
+								..Default::default()
+							},
+						image: ui_assets.button_idle.clone().into(),
+						..Default::default()
+					})
+					.insert(FocusPolicy::Pass)
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							locale.get("menu.back"),
+							TextStyle {
+								font: ui_assets.font.clone(),
+								font_size: 40.0,
+								color: ui_assets.text_color,
+							},
+						));
+					});
+				});
+		});
+}
+
+fn button_language (
+	interaction_query: Query<(&LanguageButton, &Interaction), Changed<Interaction>>,
+	mut settings: ResMut<Settings>,
+) {
+	for (button, interaction) in interaction_query.iter() {
+		if *interaction == Interaction::Clicked && settings.language != button.0 {
+			settings.language = button.0.clone();
+		}
+	}
+}
+
+fn button_language_back (
+	interaction_query: Query<(&LanguageBackButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, interaction) in interaction_query.iter() {
+		if *interaction == Interaction::Clicked {
+			state.pop().expect("Failed to change states");
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Every translation has exactly the keys of the default language
+	#[test]
+	fn locales_match_default () {
+		let reference = read_locale_file(DEFAULT_LANGUAGE).expect("Default language failed to load").strings;
+		for (code, _) in available_languages() {
+			let strings = read_locale_file(&code).expect("Language failed to load").strings;
+			let mut missing: Vec<&String> = reference.keys().filter(|key| !strings.contains_key(*key)).collect();
+			let mut unknown: Vec<&String> = strings.keys().filter(|key| !reference.contains_key(*key)).collect();
+			missing.sort();
+			unknown.sort();
+			assert!(missing.is_empty(), "Language {code} is missing {} strings: {missing:?}", missing.len());
+			assert!(unknown.is_empty(), "Language {code} has {} strings {DEFAULT_LANGUAGE} doesn't: {unknown:?}", unknown.len());
+		}
+	}
+}
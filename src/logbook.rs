@@ -1,6 +1,16 @@
 use bevy::prelude::*;
 
-use crate::{INVISIBLE, GameState, start_menu::despawn_screen, art::{SpriteSheets, spawn_spritesheet_sprite}, enums::SpriteType};
+use crate::{
+	INVISIBLE, GameState,
+	start_menu::{despawn_screen, UiAssets},
+	art::{SpriteSheets, spawn_spritesheet_sprite},
+	enums::SpriteType,
+	layout::WorldAnchor,
+	settings::Settings,
+	accessibility::{AccessibilitySettings, MoleculeSymbol},
+	save::MOLECULE_TYPES,
+	locale::Locale,
+};
 
 // ---------- PLUGINS ----------
 pub struct LogbookPlugin;
@@ -30,7 +40,8 @@ impl Plugin for LogbookPlugin {
 }
 
 // ---------- RESOURCES ----------
-
+// Ink colour of the handwriting on the logbook pages
+const INK: Color = Color::rgb(0.1, 0.1, 0.1);
 
 // ---------- COMPONENTS ----------
 #[derive(Component)]
@@ -49,6 +60,9 @@ fn load_logbook (
     mut commands: Commands,
     asset_server: Res<AssetServer>,
 	spritesheet: Res<SpriteSheets>,
+	ui_assets: Res<UiAssets>,
+	settings: Res<Settings>,
+	locale: Res<Locale>,
 ) {
 	let variant = SpriteType::LogbookButton;
 	let location = Vec3::new(668.0, -342.0, 300.0);
@@ -83,20 +97,12 @@ fn load_logbook (
 		.spawn_bundle(NodeBundle {
 			style: Style {
 				position_type: PositionType::Absolute,
-				position: UiRect {
-					// Screen width, sprite width, x translation
-					left: Val::Px(1600.0 / 2.0 - size / 2.0 + location.x),
-					// Screen height, sprite height, y translation
-					bottom: Val::Px(900.0 / 2.0 - size / 2.0 + location.y), 
-					..default()
-				},
-				// Sprite width and height
-				size: Size::new(Val::Px(size), Val::Px(size)),
 				..default()
 			},
 			color: INVISIBLE.into(),
 			..default()
 		})
+		.insert(WorldAnchor::new(location.truncate(), Vec2::splat(size)))
 		.insert(Name::new("Logbook Exit Node"))
 		.insert(LogbookUi)
 		.with_children(|parent| {
@@ -114,6 +120,57 @@ fn load_logbook (
 				})
 				.insert(LogbookExitButton);
 	});
+
+	// Key from each molecule to its name, for players who changed how molecules are told apart
+	if settings.accessibility == AccessibilitySettings::default() {
+		return;
+	}
+	for (index, molecule_type) in MOLECULE_TYPES.iter().enumerate() {
+		// Along the bottom of the right page
+		let location = Vec3::new(44.0 + index as f32 * 106.0, -350.0, 301.0);
+		let sprite = spawn_spritesheet_sprite(
+			&mut commands,
+			&spritesheet,
+			SpriteType::Molecule(*molecule_type),
+			0,
+			Color::WHITE,
+			location,
+			Vec2::splat(32.0)
+		);
+
+		commands
+		.entity(sprite)
+		.insert(LogbookSprites)
+		.insert(MoleculeSymbol {
+			molecule_type: *molecule_type,
+			size: 32.0,
+		})
+		.insert(Name::new(format!("Logbook {molecule_type:?} Key")));
+
+		commands
+			.spawn_bundle(NodeBundle {
+				style: Style {
+					position_type: PositionType::Absolute,
+					align_items: AlignItems::Center,
+					..default()
+				},
+				color: INVISIBLE.into(),
+				..default()
+			})
+			.insert(WorldAnchor::new(location.truncate() + Vec2::new(64.0, 0.0), Vec2::new(88.0, 24.0)))
+			.insert(Name::new(format!("Logbook {molecule_type:?} Key Text")))
+			.insert(LogbookUi)
+			.with_children(|parent| {
+				parent.spawn_bundle(TextBundle::from_section(
+					molecule_type.short_name(&locale),
+					TextStyle {
+						font: ui_assets.font.clone(),
+						font_size: 18.0,
+						color: INK,
+					},
+				));
+			});
+	}
 }
 
 fn button_exit_logbook (
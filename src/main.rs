@@ -2,7 +2,7 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 // Import bevy!
-use bevy::{prelude::*, render::texture::ImageSettings};
+use bevy::{prelude::*, render::texture::ImageSettings, asset::AssetServerSettings};
 
 // ---------- PLUGINS ----------
 // Modules
@@ -18,12 +18,32 @@ mod economy;
 mod endgame;
 mod enums;
 mod logbook;
+mod collector;
+mod injector;
+mod fields;
+mod damage;
+mod sales;
+mod campaign;
+mod shop;
+mod research;
+mod grid;
+mod battery;
+mod contracts;
+mod loans;
+mod save;
+mod settings;
+mod balance;
+mod profiles;
+mod controls;
+mod layout;
+mod locale;
+mod accessibility;
 
 // Plugins
 use debug::DebugPlugin;
 use camera::CameraPlugin;
 use art::ArtPlugin;
-use audio::{AudioPlugin, Volume};
+use audio::AudioPlugin;
 use start_menu::StartMenuPlugin;
 use lab::LabPlugin;
 use reactor::ReactorPlugin;
@@ -31,6 +51,26 @@ use reactor_ui::ReactorUiPlugin;
 use economy::EconomyPlugin;
 use endgame::EndgamePlugin;
 use logbook::LogbookPlugin;
+use collector::CollectorPlugin;
+use injector::InjectorPlugin;
+use fields::FieldPlugin;
+use damage::DamagePlugin;
+use sales::SalesPlugin;
+use campaign::CampaignPlugin;
+use shop::ShopPlugin;
+use research::ResearchPlugin;
+use grid::GridPlugin;
+use battery::BatteryPlugin;
+use contracts::ContractPlugin;
+use loans::LoanPlugin;
+use save::SavePlugin;
+use settings::{SettingsPlugin, SharedSettings, read_settings};
+use balance::BalancePlugin;
+use profiles::ProfilePlugin;
+use controls::ControlsPlugin;
+use layout::LayoutPlugin;
+use locale::LocalePlugin;
+use accessibility::AccessibilityPlugin;
 
 // Game State
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
@@ -39,10 +79,18 @@ pub enum GameState {
 	Lab,
 	Reactor,
 	Logbook,
+	Shop,
+	Research,
 	PauseMenu,
 	SettingsMenu,
     SettingsAudioMenu,
+	SettingsControlsMenu,
+	SettingsDisplayMenu,
+	SettingsLanguageMenu,
+	SettingsAccessibilityMenu,
+	ProfileMenu,
 	WinScreen,
+	DaySummary,
 	LoseScreen,
 	BoomScreen,
 	Credits
@@ -55,27 +103,32 @@ pub const INVISIBLE: Color = Color::NONE;
 pub const ASPECT_RATIO: f32 = 16.0 / 9.0;
 
 fn main() {
-	let height = 900.0;
+	let settings = read_settings();
 	App::new()
 	// Game States
 	.add_state(GameState::StartMenu)
 	// Resources
 	.insert_resource(ImageSettings::default_nearest())
 	.insert_resource(ClearColor(CLEAR))
+	// Hot reload assets like the balance file while debugging
+	.insert_resource(AssetServerSettings {
+		watch_for_changes: cfg!(debug_assertions),
+		..default()
+	})
 	.insert_resource(WindowDescriptor {
-		width: height * ASPECT_RATIO,
-		height: height,
+		width: settings.display.width,
+		height: settings.display.height,
 		// Working title
 		title: "Mole Rancher".to_string(),
-		present_mode: bevy::window::PresentMode::Fifo,
-		// Maybe change later
-		resizable: false,
+		present_mode: settings.display.present_mode(),
+		mode: settings.display.mode.window_mode(),
+		// The layout follows the window, see layout.rs
+		resizable: true,
 		.. Default::default()
 	})
-    .insert_resource(Volume {
-        bgm: 0.1,
-        sfx: 0.1,
-    })
+    .insert_resource(settings.volume())
+	.insert_resource(SharedSettings(settings.clone()))
+	.insert_resource(settings)
 	// Plugins
 	.add_plugins(DefaultPlugins)
 	.add_plugin(DebugPlugin)
@@ -89,5 +142,25 @@ fn main() {
 	.add_plugin(EconomyPlugin)
 	.add_plugin(EndgamePlugin)
     .add_plugin(LogbookPlugin)
+	.add_plugin(CollectorPlugin)
+	.add_plugin(InjectorPlugin)
+	.add_plugin(FieldPlugin)
+	.add_plugin(DamagePlugin)
+	.add_plugin(SalesPlugin)
+	.add_plugin(CampaignPlugin)
+	.add_plugin(ShopPlugin)
+	.add_plugin(ResearchPlugin)
+	.add_plugin(GridPlugin)
+	.add_plugin(BatteryPlugin)
+	.add_plugin(ContractPlugin)
+	.add_plugin(LoanPlugin)
+	.add_plugin(SavePlugin)
+	.add_plugin(SettingsPlugin)
+	.add_plugin(BalancePlugin)
+	.add_plugin(ProfilePlugin)
+	.add_plugin(ControlsPlugin)
+	.add_plugin(LayoutPlugin)
+	.add_plugin(LocalePlugin)
+	.add_plugin(AccessibilityPlugin)
 	.run();
 }
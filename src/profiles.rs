@@ -0,0 +1,624 @@
+use std::{fs, path::PathBuf};
+
+use bevy::{prelude::*, ui::FocusPolicy, window::ReceivedCharacter};
+use serde::{Serialize, Deserialize};
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::{UiAssets, UiButton, despawn_screen, show_buttons},
+	audio::Volume,
+	settings::{Settings, SharedSettings, AudioSettings, GameplaySettings},
+	controls::ControlSettings,
+	accessibility::AccessibilitySettings,
+	save::{CrashRecovery, FreshProgress, ProgressTargets, save_path, write_ron},
+	locale::Locale,
+};
+
+// ---------- PLUGINS ----------
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+	fn build(&self, app: &mut App) {
+		// Needed before startup so persistence knows where to look
+		let profile_list = read_profile_list();
+		let profile = ActiveProfile::load(&profile_list.active);
+
+		app
+		.insert_resource(profile_list)
+		.insert_resource(profile)
+		.insert_resource(ProfileMenu {
+			editing: None,
+			dirty: false,
+		})
+		.add_startup_system(apply_overrides)
+		.add_system_to_stage(CoreStage::PostUpdate, write_profile_on_transition)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(track_reactor_time)
+		)
+		.add_system_set(SystemSet::on_enter(GameState::DaySummary)
+			.with_system(count_day_won)
+		)
+		.add_system_set(SystemSet::on_enter(GameState::LoseScreen)
+			.with_system(count_day_lost)
+		)
+		.add_system_set(SystemSet::on_enter(GameState::BoomScreen)
+			.with_system(count_meltdown)
+		)
+		// PROFILE MENU
+		.add_system_set(SystemSet::on_enter(GameState::ProfileMenu)
+			.with_system(open_profile_menu)
+		)
+		.add_system_set(SystemSet::on_update(GameState::ProfileMenu)
+			.with_system(show_buttons)
+			.with_system(button_profile_entry)
+			.with_system(button_profile_action)
+			.with_system(edit_profile_name)
+			.with_system(load_profile_menu.after(button_profile_entry).after(button_profile_action).after(edit_profile_name))
+		)
+		.add_system_set(SystemSet::on_exit(GameState::ProfileMenu)
+			.with_system(despawn_screen::<ProfileMenuUi>)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+const PROFILE_LIST_FILE: &str = "profiles.ron";
+const PROFILE_DATA_FILE: &str = "profile.ron";
+const DEFAULT_PROFILE: &str = "Player 1";
+const MAX_NAME_LENGTH: usize = 16;
+
+// Every profile on this machine and which one is in use
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ProfileList {
+	pub active: String,
+	pub names: Vec<String>,
+}
+
+impl Default for ProfileList {
+	fn default() -> Self {
+		Self {
+			active: DEFAULT_PROFILE.to_string(),
+			names: vec![DEFAULT_PROFILE.to_string()],
+		}
+	}
+}
+
+// Read by everything that saves or loads, so each person's progress stays apart
+pub struct ActiveProfile {
+	pub name: String,
+	pub data: ProfileData,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ProfileData {
+	pub stats: ProfileStats,
+	pub overrides: SettingsOverrides,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ProfileStats {
+	pub days_won: u32,
+	pub days_lost: u32,
+	pub meltdowns: u32,
+	// Seconds spent running the reactor
+	pub reactor_time: f32,
+}
+
+// Replace the shared settings while this profile is active, display stays per machine
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SettingsOverrides {
+	pub audio: Option<AudioSettings>,
+	pub gameplay: Option<GameplaySettings>,
+	pub controls: Option<ControlSettings>,
+	pub accessibility: Option<AccessibilitySettings>,
+}
+
+impl ActiveProfile {
+	pub fn load(name: &str) -> Self {
+		let data = fs::read_to_string(profile_dir(name).join(PROFILE_DATA_FILE))
+			.ok()
+			.and_then(|text| ron::from_str(&text).map_err(|error| warn!("Ignoring unreadable profile {name}: {error}")).ok())
+			.unwrap_or_default();
+		Self {
+			name: name.to_string(),
+			data,
+		}
+	}
+
+	pub fn dir(&self) -> PathBuf {
+		profile_dir(&self.name)
+	}
+
+	pub fn write(&self) {
+		write_ron(&self.dir().join(PROFILE_DATA_FILE), &self.data);
+	}
+
+	// Layers this profile's overrides over the shared settings
+	pub fn apply_overrides(&self, settings: &mut Settings) {
+		if let Some(audio) = &self.data.overrides.audio {
+			settings.audio = audio.clone();
+		}
+		if let Some(gameplay) = &self.data.overrides.gameplay {
+			settings.gameplay = gameplay.clone();
+		}
+		if let Some(controls) = &self.data.overrides.controls {
+			settings.controls = controls.clone();
+		}
+		if let Some(accessibility) = &self.data.overrides.accessibility {
+			settings.accessibility = accessibility.clone();
+		}
+	}
+}
+
+// Set while the profile menu is open
+pub struct ProfileMenu {
+	// Name being typed for the active profile
+	pub editing: Option<String>,
+	// Rebuild the menu on the next update
+	pub dirty: bool,
+}
+
+// ---------- COMPONENTS ----------
+#[derive(Component)]
+struct ProfileMenuUi;
+
+#[derive(Component)]
+struct ProfileEntryButton(usize);
+
+#[derive(Component, Debug, PartialEq, Clone, Copy)]
+enum ProfileAction {
+	New,
+	Rename,
+	Delete,
+	Back,
+}
+
+// ---------- SYSTEMS ----------
+pub fn profile_dir (
+	name: &str,
+) -> PathBuf {
+	save_path("profiles").join(name)
+}
+
+fn read_profile_list () -> ProfileList {
+	let mut profile_list = fs::read_to_string(save_path(PROFILE_LIST_FILE))
+		.ok()
+		.and_then(|text| ron::from_str::<ProfileList>(&text).map_err(|error| warn!("Ignoring unreadable profile list: {error}")).ok())
+		.unwrap_or_default();
+	if profile_list.names.is_empty() {
+		profile_list = ProfileList::default();
+	}
+	if !profile_list.names.contains(&profile_list.active) {
+		profile_list.active = profile_list.names[0].clone();
+	}
+	profile_list
+}
+
+fn write_profile_list (
+	profile_list: &ProfileList,
+) {
+	write_ron(&save_path(PROFILE_LIST_FILE), profile_list);
+}
+
+// Letters, digits, spaces, dashes and underscores keep names safe as directory names
+fn valid_name_character (
+	character: char,
+) -> bool {
+	character.is_alphanumeric() || character == ' ' || character == '-' || character == '_'
+}
+
+fn apply_overrides (
+	profile: Res<ActiveProfile>,
+	mut settings: ResMut<Settings>,
+	mut volume: ResMut<Volume>,
+) {
+	profile.apply_overrides(&mut settings);
+	*volume = settings.volume();
+}
+
+// Stats only hit the disk on state changes, not every frame
+// State is written to every frame by its driver, so the last one seen is compared instead
+fn write_profile_on_transition (
+	state: Res<State<GameState>>,
+	mut last_state: Local<Option<GameState>>,
+	profile: Res<ActiveProfile>,
+) {
+	let current = *state.current();
+	if *last_state == Some(current) {
+		return;
+	}
+	let first_frame = last_state.is_none();
+	*last_state = Some(current);
+	if !first_frame {
+		profile.write();
+	}
+}
+
+fn track_reactor_time (
+	time: Res<Time>,
+	mut profile: ResMut<ActiveProfile>,
+) {
+	profile.data.stats.reactor_time += time.delta_seconds();
+}
+
+fn count_day_won (
+	mut profile: ResMut<ActiveProfile>,
+) {
+	profile.data.stats.days_won += 1;
+}
+
+fn count_day_lost (
+	mut profile: ResMut<ActiveProfile>,
+) {
+	profile.data.stats.days_lost += 1;
+}
+
+fn count_meltdown (
+	mut profile: ResMut<ActiveProfile>,
+) {
+	profile.data.stats.meltdowns += 1;
+}
+
+// Saves the outgoing profile, then swaps in the new one's settings and a clean slate of progress
+fn select_profile (
+	name: &str,
+	profile_list: &mut ProfileList,
+	profile: &mut ActiveProfile,
+	shared: &SharedSettings,
+	settings: &mut Settings,
+	volume: &mut Volume,
+	fresh_progress: &FreshProgress,
+	targets: &mut ProgressTargets,
+) {
+	profile.write();
+	*profile = ActiveProfile::load(name);
+	profile_list.active = name.to_string();
+	write_profile_list(profile_list);
+
+	// Start from the shared settings so the outgoing profile's overrides don't carry over
+	let mut effective = shared.0.clone();
+	profile.apply_overrides(&mut effective);
+	*settings = effective;
+	*volume = settings.volume();
+	targets.apply(&fresh_progress.0, false);
+}
+
+fn open_profile_menu (
+	mut profile_menu: ResMut<ProfileMenu>,
+) {
+	profile_menu.editing = None;
+	profile_menu.dirty = true;
+}
+
+// Rebuilt whenever a profile is added, removed, renamed or selected
+fn load_profile_menu (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	profile_list: Res<ProfileList>,
+	profile: Res<ActiveProfile>,
+	locale: Res<Locale>,
+	mut profile_menu: ResMut<ProfileMenu>,
+	ui_query: Query<Entity, With<ProfileMenuUi>>,
+) {
+	if !profile_menu.dirty {
+		return;
+	}
+	profile_menu.dirty = false;
+	for entity in ui_query.iter() {
+		commands.entity(entity).despawn_recursive();
+	}
+
+	let button_style = Style {
+		size: Size::new(Val::Px(250.0), Val::Px(65.0)),
+		margin: UiRect::all(Val::Px(10.0)),
+		justify_content: JustifyContent::Center,
+		align_items: AlignItems::Center,
+		..default()
+	};
+
+	let button_text_style = TextStyle {
+		font: ui_assets.font.clone(),
+		font_size: 32.0,
+		color: ui_assets.text_color,
+	};
+
+	let stats = &profile.data.stats;
+	let stats_text = locale.format("profiles.stats", &[
+		("won", &stats.days_won),
+		("lost", &stats.days_lost),
+		("meltdowns", &stats.meltdowns),
+		("minutes", &format!("{:.0}", stats.reactor_time / 60.0)),
+	]);
+
+	commands
+		// Node Bundle to hold the Profile Menu UI elements
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				margin: UiRect::all(Val::Auto),
+				flex_direction: FlexDirection::ColumnReverse,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(ProfileMenuUi)
+		.insert(Name::new("Profile Menu UI"))
+		.with_children(|parent| {
+
+			// Title
+			parent.spawn_bundle(
+				TextBundle::from_section(
+					locale.get("profiles.title"),
+					TextStyle {
+						font: ui_assets.font.clone(),
+						font_size: 80.0,
+						color: ui_assets.text_color,
+					},
+				)
+				.with_style(Style {
+					margin: UiRect::all(Val::Px(20.0)),
+					..default()
+				}),
+			);
+
+			// Profile Entries, the active one shows its stats
+			for (index, name) in profile_list.names.iter().enumerate() {
+				let active = *name == profile.name;
+				let label = match (&profile_menu.editing, active) {
+					(Some(editing), true) => format!("> {editing}_"),
+					(None, true) => format!("> {name}"),
+					_ => name.clone(),
+				};
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: Style {
+							margin: UiRect::all(Val::Px(5.0)),
+							flex_direction: FlexDirection::ColumnReverse,
+							align_items: AlignItems::Center,
+							..default()
+						},
+						color: INVISIBLE.into(),
+						..default()
+					})
+					.insert(ProfileEntryButton(index))
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							label,
+							TextStyle {
+								font: ui_assets.font.clone(),
+								font_size: 40.0,
+								color: if active {Color::rgb(0.0, 0.5, 0.0)} else {ui_assets.text_color},
+							},
+						));
+						if active {
+							parent.spawn_bundle(TextBundle::from_section(
+								stats_text.clone(),
+								TextStyle {
+									font: ui_assets.font.clone(),
+									font_size: 20.0,
+									color: ui_assets.text_color,
+								},
+							));
+						}
+					});
+			}
+
+			// Action Buttons
+			parent
+				.spawn_bundle(NodeBundle {
+					style: Style {
+						margin: UiRect::all(Val::Px(20.0)),
+						..default()
+					},
+					color: INVISIBLE.into(),
+					..default()
+				})
+				.with_children(|parent| {
+					for (action, label) in [
+						(ProfileAction::New, locale.get("profiles.new")),
+						(ProfileAction::Rename, locale.get(if profile_menu.editing.is_some() {"profiles.done"} else {"profiles.rename"})),
+						(ProfileAction::Delete, locale.get("profiles.delete")),
+						(ProfileAction::Back, locale.get("menu.back")),
+					] {
+						parent
+							.spawn_bundle(ButtonBundle {
+								style: button_style.clone(),
+								color: ui_assets.button_color.into(),
+								..default()
+							})
+							.insert(action)
+							.insert(UiButton)
+							.with_children(|parent| {
+
+								// Action Button Sprite
+								parent
+									.spawn_bundle(ImageBundle {
+										style: Style {
+											size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+											justify_content: JustifyContent::Center,
+											align_items: AlignItems::Center,
+											..Default::default()
+										},
+									image: ui_assets.button_idle.clone().into(),
+									..Default::default()
+								})
+								.insert(FocusPolicy::Pass)
+								.with_children(|parent| {
+									parent.spawn_bundle(TextBundle::from_section(
+										label,
+										button_text_style.clone(),
+									));
+								});
+							});
+					}
+				});
+		});
+}
+
+fn button_profile_entry (
+	interaction_query: Query<(&ProfileEntryButton, &Interaction), Changed<Interaction>>,
+	mut profile_list: ResMut<ProfileList>,
+	mut profile: ResMut<ActiveProfile>,
+	mut profile_menu: ResMut<ProfileMenu>,
+	shared: Res<SharedSettings>,
+	mut settings: ResMut<Settings>,
+	mut volume: ResMut<Volume>,
+	mut crash_recovery: ResMut<CrashRecovery>,
+	fresh_progress: Res<FreshProgress>,
+	mut targets: ProgressTargets,
+) {
+	for (entry, interaction) in interaction_query.iter() {
+		if *interaction != Interaction::Clicked || profile_menu.editing.is_some() {
+			continue;
+		}
+		let name = match profile_list.names.get(entry.0) {
+			Some(name) if *name != profile.name => name.clone(),
+			_ => continue,
+		};
+		select_profile(&name, &mut profile_list, &mut profile, &shared, &mut settings, &mut volume, &fresh_progress, &mut targets);
+		// The crash being offered belonged to the previous profile
+		crash_recovery.available = false;
+		profile_menu.dirty = true;
+	}
+}
+
+fn button_profile_action (
+	interaction_query: Query<(&ProfileAction, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+	mut profile_list: ResMut<ProfileList>,
+	mut profile: ResMut<ActiveProfile>,
+	mut profile_menu: ResMut<ProfileMenu>,
+	shared: Res<SharedSettings>,
+	mut settings: ResMut<Settings>,
+	mut volume: ResMut<Volume>,
+	mut crash_recovery: ResMut<CrashRecovery>,
+	fresh_progress: Res<FreshProgress>,
+	mut targets: ProgressTargets,
+) {
+	for (action, interaction) in interaction_query.iter() {
+		if *interaction != Interaction::Clicked {
+			continue;
+		}
+		match action {
+			// Starts as the first free "Player N" and goes straight into naming it
+			ProfileAction::New => {
+				if profile_menu.editing.is_some() {
+					continue;
+				}
+				let name = (1..)
+					.map(|number| format!("Player {number}"))
+					.find(|name| !profile_list.names.contains(name))
+					.unwrap();
+				profile_list.names.push(name.clone());
+				select_profile(&name, &mut profile_list, &mut profile, &shared, &mut settings, &mut volume, &fresh_progress, &mut targets);
+				crash_recovery.available = false;
+				profile_menu.editing = Some(name);
+			}
+			ProfileAction::Rename => {
+				profile_menu.editing = match profile_menu.editing.take() {
+					Some(editing) => {
+						rename_profile(&editing, &mut profile_list, &mut profile);
+						None
+					}
+					None => Some(profile.name.clone()),
+				};
+			}
+			// The last profile can't go, there has to be someone to play as
+			ProfileAction::Delete => {
+				if profile_menu.editing.is_some() || profile_list.names.len() <= 1 {
+					continue;
+				}
+				let _ = fs::remove_dir_all(profile.dir());
+				let deleted = profile.name.clone();
+				profile_list.names.retain(|name| *name != deleted);
+				let name = profile_list.names[0].clone();
+				// Swapped in first so selecting doesn't write the deleted profile back to disk
+				*profile = ActiveProfile::load(&name);
+				select_profile(&name, &mut profile_list, &mut profile, &shared, &mut settings, &mut volume, &fresh_progress, &mut targets);
+				crash_recovery.available = false;
+			}
+			ProfileAction::Back => {
+				if let Some(editing) = profile_menu.editing.take() {
+					rename_profile(&editing, &mut profile_list, &mut profile);
+				}
+				state.pop().expect("Failed to change states");
+			}
+		}
+		profile_menu.dirty = true;
+	}
+}
+
+// Moves the profile's directory along with its name, keeps the old name if the new one is taken or empty
+fn rename_profile (
+	new_name: &str,
+	profile_list: &mut ProfileList,
+	profile: &mut ActiveProfile,
+) {
+	let new_name = new_name.trim();
+	if new_name.is_empty() || new_name == profile.name || profile_list.names.iter().any(|name| name == new_name) {
+		return;
+	}
+
+	let old_dir = profile.dir();
+	let new_dir = profile_dir(new_name);
+	if old_dir.exists() {
+		if let Err(error) = fs::rename(&old_dir, &new_dir) {
+			warn!("Failed to rename profile {}: {error}", profile.name);
+			return;
+		}
+	}
+
+	for name in profile_list.names.iter_mut() {
+		if *name == profile.name {
+			*name = new_name.to_string();
+		}
+	}
+	profile.name = new_name.to_string();
+	profile_list.active = profile.name.clone();
+	profile.write();
+	write_profile_list(profile_list);
+}
+
+// Typing goes into the name being edited, Enter keeps it and Escape drops it
+fn edit_profile_name (
+	mut characters: EventReader<ReceivedCharacter>,
+	keyboard: Res<Input<KeyCode>>,
+	mut profile_list: ResMut<ProfileList>,
+	mut profile: ResMut<ActiveProfile>,
+	mut profile_menu: ResMut<ProfileMenu>,
+) {
+	let mut editing = match profile_menu.editing.clone() {
+		Some(editing) => editing,
+		None => {
+			characters.clear();
+			return;
+		}
+	};
+
+	for event in characters.iter() {
+		if valid_name_character(event.char) && editing.chars().count() < MAX_NAME_LENGTH {
+			editing.push(event.char);
+		}
+	}
+	if keyboard.just_pressed(KeyCode::Back) {
+		editing.pop();
+	}
+
+	if keyboard.just_pressed(KeyCode::Return) {
+		rename_profile(&editing, &mut profile_list, &mut profile);
+		profile_menu.editing = None;
+		profile_menu.dirty = true;
+	} else if keyboard.just_pressed(KeyCode::Escape) {
+		profile_menu.editing = None;
+		profile_menu.dirty = true;
+	} else if profile_menu.editing.as_ref() != Some(&editing) {
+		profile_menu.editing = Some(editing);
+		profile_menu.dirty = true;
+	}
+}
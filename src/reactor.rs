@@ -1,15 +1,22 @@
 use bevy::prelude::*;
 use bevy_inspector_egui::Inspectable;
-use rand::{Rng, random};
+use rand::random;
 
 use crate::{
 	GameState,
 	art::{spawn_spritesheet_sprite, SpriteSheets}, 
-	enums::{MoleculeType, SpriteType, ReactionType},
+	enums::{MoleculeType, SpriteType, ReactionType, WallMaterial},
 	start_menu::despawn_screen, 
 	reactor_ui::Reactor, 
-	economy::{Economy, Power, Temperature, PRESSURE_SENSITIVITY}, 
-	endgame::BasicCountdown
+	economy::{Economy, Temperature, Integrity}, 
+	endgame::BasicCountdown,
+	fields::FieldEmitter,
+	shop::Upgrades,
+	research::{Research, ResearchEffect},
+	battery::Battery,
+	balance::BalanceConfig,
+	controls::{Actions, InputAction, GameSpeed},
+	accessibility::MoleculeSymbol,
 };
 
 // ---------- PLUGINS ----------
@@ -43,7 +50,10 @@ impl Plugin for ReactorPlugin {
 
 // ---------- RESOURCES ----------
 
-const ANIMATION_SPEED: f32 = 0.125;
+// Integrity lost per thermometer tick at exactly max pressure
+const INTEGRITY_DAMAGE: f32 = 10.0;
+// Speed conducting walls pull bouncing molecules towards
+const AMBIENT_SPEED: f32 = 2.0;
 
 // ---------- COMPONENTS ----------
 #[derive(Component, Inspectable)]
@@ -83,37 +93,6 @@ impl Cost {
 #[derive(Component, Inspectable)]
 pub struct GodMode(pub bool);
 
-#[derive(Component, Inspectable)]
-pub struct Unlocked {
-	pub red_unlocked: bool,
-	pub blue_unlocked: bool,
-    pub orange_unlocked: bool,
-    pub purple_unlocked: bool,
-    pub gray_unlocked: bool,
-}
-
-impl Unlocked {  
-    pub fn get_unlocked(&self, molecule_type: MoleculeType) -> &bool {
-        match molecule_type {
-			MoleculeType::Red => &self.red_unlocked,
-			MoleculeType::Blue => &self.blue_unlocked,
-			MoleculeType::Orange => &self.orange_unlocked,
-            MoleculeType::Purple => &self.purple_unlocked,
-            MoleculeType::Gray => &self.gray_unlocked,
-		}
-    }
-
-	pub fn unlock(&mut self, molecule_type: MoleculeType) {
-		match molecule_type {
-			MoleculeType::Red => self.red_unlocked = true,
-			MoleculeType::Blue => self.blue_unlocked = true,
-			MoleculeType::Orange => self.orange_unlocked = true,
-            MoleculeType::Purple => self.purple_unlocked = true,
-            MoleculeType::Gray => self.gray_unlocked = true,
-		}
-	}
-}
-
 #[derive(Component, Inspectable)]
 pub struct Molecule {
 	pub variant: MoleculeType,
@@ -134,6 +113,12 @@ pub struct Velocity
 	pub val: Vec2
 }
 
+// Installed wall material, kept between days
+#[derive(Component, Inspectable)]
+pub struct ReactorWalls {
+	pub material: WallMaterial,
+}
+
 #[derive(Component)]
 struct Thermometer;
 
@@ -143,10 +128,11 @@ pub struct AnimationTimer;
 // ---------- SYSTEMS ----------
 fn generate_animation_timer (
 	mut commands: Commands,
+	balance: Res<BalanceConfig>,
 ) {
 	commands
 		.spawn()
-		.insert(BasicCountdown(Timer::from_seconds(ANIMATION_SPEED, true)))
+		.insert(BasicCountdown(Timer::from_seconds(balance.animation_speed, true)))
 		.insert(Name::new("Animation Refresh Rate Timer"))
 		.insert(Reactor)
 		.insert(AnimationTimer);
@@ -185,140 +171,105 @@ pub fn generate_molecule_list (
 // Check please
 fn product_molecule (
     molecule_type: MoleculeType,
-    mut commands: &mut Commands,
+    commands: &mut Commands,
     spritesheet: &Res<SpriteSheets>,
     molecule_list_query: &mut Query<&MoleculeList>,
     location: Vec3,
+    velocity_factor: f32,
 ) {
-    let molecule_list = molecule_list_query.single_mut();
+	let velocity = random_velocity(molecule_type.mass(), velocity_factor);
+	spawn_molecule(molecule_type, commands, spritesheet, molecule_list_query, 0, location, velocity);
+}
+
+fn random_velocity (
+	mass: f32,
+	velocity_factor: f32,
+) -> Vec2 {
+	Vec2::new(
+		velocity_factor * (2.0 * random::<f32>() - 1.0).signum() * ((2.0 * random::<f32>() - 1.0).abs() + random::<f32>() - 0.5).clamp(0.0, 1.0) / mass,
+		velocity_factor * (2.0 * random::<f32>() - 1.0).signum() * ((2.0 * random::<f32>() - 1.0).abs() + random::<f32>() - 0.5).clamp(0.0, 1.0) / mass
+	)
+}
 
-    let molecule = spawn_spritesheet_sprite(
+// Shared by every way a molecule can enter the reactor
+pub fn spawn_molecule (
+	molecule_type: MoleculeType,
+	mut commands: &mut Commands,
+	spritesheet: &Res<SpriteSheets>,
+	molecule_list_query: &mut Query<&MoleculeList>,
+	animation_index: usize,
+	location: Vec3,
+	velocity: Vec2,
+) -> Entity {
+	let molecule_list = molecule_list_query.single_mut();
+
+	let molecule = spawn_spritesheet_sprite(
 		&mut commands,
 		&spritesheet,
 		SpriteType::Molecule(molecule_type),
-		0,
+		animation_index,
 		Color::WHITE,
 		location,
 		Vec2::splat(32.0)
 	);
 
-	let mass: f32 = molecule_type.mass();
-
 	commands
 	.entity(molecule)
-	.insert(Name::new(molecule_type.name()))
+	.insert(Name::new(format!("{molecule_type:?} Molecule")))
 	.insert(Molecule {
 		variant: molecule_type,
 		mass: molecule_type.mass(),
 		reacted: false
 	})
-	.insert(Velocity {val: Vec2::new( // TODO: smarter value than just "4.0"
-		4.0 * (2.0 * random::<f32>() - 1.0).signum() * ((2.0 * random::<f32>() - 1.0).abs() + random::<f32>() - 0.5).clamp(0.0, 1.0) / mass,
-		4.0 * (2.0 * random::<f32>() - 1.0).signum() * ((2.0 * random::<f32>() - 1.0).abs() + random::<f32>() - 0.5).clamp(0.0, 1.0) / mass
-	)})
+	.insert(Velocity {val: velocity})
 	.insert(Dimensions {
 		x_size: 32.0,
 		y_size: 32.0
+	})
+	.insert(MoleculeSymbol {
+		molecule_type,
+		size: 32.0,
 	});
 
 	commands
 	.entity(molecule_list.0)
 	.push_children(&[molecule]);
+
+	molecule
 }
 
 pub fn generate_unlock_list (
 	mut commands: Commands,
 ) {
 	commands
-    .spawn()
-    .insert( Unlocked {
-        red_unlocked: true,
-        blue_unlocked: true,
-        orange_unlocked: false,
-        purple_unlocked: false,
-        gray_unlocked: false,
-    })
-    .insert(Name::new("Unlocked"));
-
-	commands
 	.spawn()
 	.insert(GodMode(false))
 	.insert(Name::new("God Mode"));
+
+	commands
+	.spawn()
+	.insert(ReactorWalls {
+		material: WallMaterial::Steel,
+	})
+	.insert(Name::new("Reactor Walls"));
 }
 
 fn check_godmode (
-	keyboard: Res<Input<KeyCode>>,
+	actions: Actions,
 	mut godmode_query: Query<&mut GodMode>,
-	mut unlocked_query: Query<&mut Unlocked>,
+	mut research_query: Query<&mut Research>,
 ) {
 	let mut godmode = godmode_query.single_mut();
-	let mut unlocked = unlocked_query.single_mut();
-	if keyboard.just_pressed(KeyCode::G) {
+	let mut research = research_query.single_mut();
+	if actions.just_pressed(InputAction::ToggleGodMode) {
 		godmode.0 = !godmode.0;
 	}
 	if godmode.0 {
-		unlocked.unlock(MoleculeType::Red);
-		unlocked.unlock(MoleculeType::Blue);
-		unlocked.unlock(MoleculeType::Orange);
-		unlocked.unlock(MoleculeType::Purple);
-		unlocked.unlock(MoleculeType::Gray);
+		research.grant_all();
 	}
 
 }
 
-pub fn add_molecule (
-	molecule_type: MoleculeType,
-	mut commands: &mut Commands,
-	spritesheet: &Res<SpriteSheets>,
-	molecule_list_query: &mut Query<&MoleculeList>,
-	reactor_query: &Query<(&Reactor, &Dimensions, &Transform)>
-) {
-	let (_reactor, dimensions, transform) = reactor_query.single();
-
-	let molecule_list = molecule_list_query.single_mut();
-
-	// Make this global resource?
-	let mut rng = rand::thread_rng();
-	let molecule = spawn_spritesheet_sprite(
-		&mut commands,
-		&spritesheet,
-		SpriteType::Molecule(molecule_type),
-		rng.gen_range(0..8),
-		Color::WHITE,
-		//Color::rgb(random::<f32>(), random::<f32>(), random::<f32>()),
-		// Find some way to pass Reactor to spawn molecule inside and bound movement within
-		// Pass reactor index to function, use reactor index to find reactor size from transform.scale
-		Vec3::new(
-		(dimensions.x_size * random::<f32>() - (dimensions.x_size / 2.0)) + transform.translation.x, 
-		(dimensions.y_size * random::<f32>() - (dimensions.y_size / 2.0)) + transform.translation.y, 
-		900.0),
-		Vec2::splat(32.0)
-	);
-
-	let mass: f32 = molecule_type.mass();
-
-	commands
-	.entity(molecule)
-	.insert(Name::new(molecule_type.name()))
-	.insert(Molecule {
-		variant: molecule_type,
-		mass: molecule_type.mass(),
-		reacted: false
-	})
-	.insert(Velocity {val: Vec2::new( // TODO: smarter value than just "4.0"
-		4.0 * (2.0 * random::<f32>() - 1.0).signum() * ((2.0 * random::<f32>() - 1.0).abs() + random::<f32>() - 0.5).clamp(0.0, 1.0) / mass,
-		4.0 * (2.0 * random::<f32>() - 1.0).signum() * ((2.0 * random::<f32>() - 1.0).abs() + random::<f32>() - 0.5).clamp(0.0, 1.0) / mass
-	)})
-	.insert(Dimensions {
-		x_size: 32.0,
-		y_size: 32.0
-	});
-
-	commands
-	.entity(molecule_list.0)
-	.push_children(&[molecule]);
-}
-
 pub fn add_control_rod (
 	mut commands: Commands,
 	entities: Query<Entity, With<Molecule>>,
@@ -334,16 +285,31 @@ fn molecule_movement (
 	mut reactor_query: Query<(&Reactor, &Dimensions, &Transform), Without<Molecule>>,
 	mut molecule_query: Query<(Entity, &mut Molecule, &mut Velocity, &Dimensions, &mut Transform)>,
 	mut molecule_list_query: Query<&MoleculeList>,
-	mut economy_query: Query<(&Economy, &mut Power, &mut Temperature)>,
-	mut unlocked_query: Query<&mut Unlocked>,
+	mut economy_query: Query<(&Economy, &mut Temperature)>,
+	mut battery_query: Query<&mut Battery>,
+	mut research_query: Query<&mut Research>,
+	field_query: Query<(&FieldEmitter, &Transform), Without<Molecule>>,
+	walls_query: Query<&ReactorWalls>,
+	upgrades_query: Query<&Upgrades>,
+	balance: Res<BalanceConfig>,
+	speed: Res<GameSpeed>,
 ) {
-	let (_economy, mut power, mut pressure) = economy_query.single_mut();
-	let mut unlocked = unlocked_query.single_mut();
+	// Everything holds still while paused
+	if speed.paused {
+		return;
+	}
+	let step = speed.scale();
+	let (_economy, mut pressure) = economy_query.single_mut();
+	let mut battery = battery_query.single_mut();
+	let mut research = research_query.single_mut();
+	let walls = walls_query.single();
+	let restitution = walls.material.restitution();
+	let conductivity = (walls.material.conductivity() + upgrades_query.single().cooling()).min(1.0);
 
 	// Remove single mut when adding more reactors
 	let (_reactor, reactor_size, reactor_transform) = reactor_query.single_mut();
 
-	let collision_radius = 32.0;
+	let collision_radius = balance.collision_radius;
 
 	let mut iter = molecule_query.iter_combinations_mut();
 	while let Some([
@@ -357,32 +323,33 @@ fn molecule_movement (
 		let offset = transform_a.translation - transform_b.translation;
 		let offset = Vec2::new(offset.x, offset.y);
 		if offset.length_squared() <= collision_radius * collision_radius {
-			if let Some(reaction) = MoleculeType::can_react(molecule_a.variant, molecule_b.variant) {
+			// Reactions that haven't been researched yet just bounce
+			let reaction = MoleculeType::can_react(molecule_a.variant, molecule_b.variant)
+				.filter(|reaction| research.is_unlocked(ResearchEffect::Reaction(reaction.reaction_type)));
+			if let Some(reaction) = reaction {
 				let mut reaction_success = false;
 				match reaction.reaction_type {
 					ReactionType::RedBlue | ReactionType::GrayPurple | ReactionType::GrayRed | ReactionType::GrayPurple => {
 						// If temp > xyz or something
 						for product in &reaction.products {
 							// Change location to midpoint between molecules?
-							product_molecule(*product, &mut commands, &spritesheet, &mut molecule_list_query, transform_a.translation);
+							product_molecule(*product, &mut commands, &spritesheet, &mut molecule_list_query, transform_a.translation, balance.velocity_factor);
 							reaction_success = true;}
 					}
 					ReactionType::BlueBlue | ReactionType::OrangeOrange | ReactionType::GrayOrange => {
 						for product in &reaction.products {
 							let loc_rng = Vec3::new(random::<f32>(), random::<f32>(),random::<f32>());
 							// Change location to midpoint between molecules?
-							product_molecule(*product, &mut commands, &spritesheet, &mut molecule_list_query, transform_a.translation + loc_rng);
+							product_molecule(*product, &mut commands, &spritesheet, &mut molecule_list_query, transform_a.translation + loc_rng, balance.velocity_factor);
 							reaction_success = true;}
 					}
 				}
 
 				if reaction_success {
-					for product in reaction.products {
-						if !unlocked.get_unlocked(product) {
-							unlocked.unlock(product);
-						}
-					}
-					power.current_power = (power.current_power + reaction.power_generated).clamp(0.0, 999000.0);
+					// New products are studied rather than unlocked outright
+					research.record_synthesis(&reaction.products);
+					// Energy goes to the battery, which decides how much of it is kept
+					battery.incoming += reaction.power_generated;
 					//pressure.current_pressure = (pressure.current_pressure + reaction.temp_generated).clamp(0.0, MAX_PRESSURE);
 					// Molecules that react are despawned anyway
 					molecule_a.reacted = true;
@@ -423,26 +390,34 @@ fn molecule_movement (
 			transform.translation.y = transform.translation.y + velocity.val.y.signum() * ((velocity.val.y).abs() + random::<f32>() - 0.5).clamp(0.0, 8.0);
 		}
 		else {
-			let x_target = transform.translation.x + velocity.val.x;
+			// Field emitters accelerate molecules by charge over mass
+			for (emitter, emitter_transform) in field_query.iter() {
+				let force = emitter.force_at(emitter_transform.translation.truncate(), transform.translation.truncate());
+				velocity.val += step * molecule.variant.charge() * force / molecule.mass;
+			}
+
+			let x_target = transform.translation.x + velocity.val.x * step;
 			// If molecule won't hit off of the edges
 			if (x_target - reactor_transform.translation.x).abs() <= (reactor_size.x_size  - molecule_size.x_size) / 2.0 {
 				transform.translation.x = x_target;
 			}
 			// If molecule will hit off of the edges
 			else {
-				velocity.val.x = -velocity.val.x;
-				delta_momentum += (2.0 * velocity.val.x * molecule.mass).abs();
+				delta_momentum += ((1.0 + restitution) * velocity.val.x * molecule.mass).abs();
+				velocity.val.x = -velocity.val.x * restitution;
+				velocity.val = conduct_heat(velocity.val, conductivity);
 			}
 
-			let y_target = transform.translation.y + velocity.val.y;
+			let y_target = transform.translation.y + velocity.val.y * step;
 			// If molecule won't hit off of the top or bottom
 			if (y_target - reactor_transform.translation.y).abs() <= (reactor_size.y_size - molecule_size.y_size) / 2.0 {
 				transform.translation.y = y_target;
 			}
 			// If molecule will hit off of the top or bottom
 			else {
-				velocity.val.y = -velocity.val.y;
-				delta_momentum += (2.0 * velocity.val.y * molecule.mass).abs();
+				delta_momentum += ((1.0 + restitution) * velocity.val.y * molecule.mass).abs();
+				velocity.val.y = -velocity.val.y * restitution;
+				velocity.val = conduct_heat(velocity.val, conductivity);
 			}
 		}
 
@@ -450,7 +425,7 @@ fn molecule_movement (
 		// 1 = updates completely every frame
 		// 0 = doesnt do anything
 		// /60 is to try to compensate for framerate
-		let lerp_factor = PRESSURE_SENSITIVITY / 60.0;
+		let lerp_factor = (balance.pressure_sensitivity * step / 60.0).min(1.0);
 
 		pressure.current_pressure *= 1.0 - lerp_factor;
 		// The * 60 is because of the short timespan of the collision = large force
@@ -462,13 +437,23 @@ fn molecule_movement (
 	}
 }
 
+// Pulls a bouncing molecule's speed towards the wall's, keeping its direction
+fn conduct_heat (
+	velocity: Vec2,
+	conductivity: f32,
+) -> Vec2 {
+	let speed = velocity.length();
+	velocity.normalize_or_zero() * (speed + (AMBIENT_SPEED - speed) * conductivity)
+}
+
 fn molecule_animation (
 	time: Res<Time>,
+	speed: Res<GameSpeed>,
 	mut molecule_query: Query<(&Molecule, &mut TextureAtlasSprite)>,
 	mut animation_timer_query: Query<(&AnimationTimer, &mut BasicCountdown)>,
 ) {
 	let (_animation_timer, mut countdown) = animation_timer_query.single_mut();
-	if countdown.0.tick(time.delta()).just_finished() {
+	if countdown.0.tick(speed.delta(&time)).just_finished() {
 		for (molecule, mut sprite) in molecule_query.iter_mut() {
 			sprite.index += 1;
 			sprite.index %= molecule.variant.animation_frames();
@@ -503,20 +488,26 @@ fn generate_thermometer (
 
 fn update_thermometer (
 	time: Res<Time>,
+	speed: Res<GameSpeed>,
 	//molecule_query: Query<(&Molecule, &Velocity)>,
 	mut state: ResMut<State<GameState>>,
 	mut thermometer_query: Query<(&Thermometer, &mut BasicCountdown, &mut TextureAtlasSprite)>,
-	mut economy_query: Query<(&Economy, &mut Temperature)>,
+	mut economy_query: Query<(&Economy, &mut Temperature, &mut Integrity)>,
 	godmode_query: Query<&GodMode>
 ) {
 	let godmode = godmode_query.single();
 	let (_thermometer, mut cooling_countdown, mut thermometer_sprite) = thermometer_query.single_mut();
-	let (_economy, pressure) = economy_query.single_mut();
+	let (_economy, pressure, mut integrity) = economy_query.single_mut();
 
 	if !godmode.0 {
-		if cooling_countdown.0.tick(time.delta()).just_finished() {
-			// If current pressure above max then cut to boomScreen
+		if cooling_countdown.0.tick(speed.delta(&time)).just_finished() {
+			// If current pressure above max then damage the reactor, harder the further over it is
 			if pressure.current_pressure >= pressure.max_pressure {
+				integrity.current_integrity -= INTEGRITY_DAMAGE * pressure.current_pressure / pressure.max_pressure;
+			}
+			// Only melt down once the reactor has nothing left
+			if integrity.current_integrity <= 0.0 {
+				integrity.current_integrity = 0.0;
 				state.push(GameState::BoomScreen).expect("Failed to change states");
 			}
 			// Otherwise subtract passive cooling
@@ -3,10 +3,18 @@ use bevy::{prelude::*, ui::FocusPolicy};
 use crate::{
 	GameState, 
 	start_menu::{despawn_screen, UiAssets}, 
-	reactor::{Dimensions, MoleculeList, add_molecule, add_control_rod, Molecule, Cost, AnimationTimer, Unlocked, GodMode},
-	art::{SpriteSheets, spawn_spritesheet_sprite}, economy::{Economy, Power, Clock},
+	reactor::{Dimensions, add_control_rod, Molecule, Cost, AnimationTimer, GodMode},
+	art::{SpriteSheets, spawn_spritesheet_sprite}, economy::{Economy, Power, Clock, Market, Trend, CashFlow},
 	INVISIBLE,
-	enums::{MoleculeType, SpriteType}, endgame::BasicCountdown
+	enums::{MoleculeType, SpriteType}, endgame::BasicCountdown,
+	injector::Injector,
+	campaign::Campaign,
+	shop::Upgrades,
+	research::Research,
+	controls::{Actions, InputAction, INPUT_ACTIONS, GameSpeed},
+	layout::WorldAnchor,
+	locale::Locale,
+	accessibility::MoleculeSymbol,
 };
 
 // ---------- PLUGINS ----------
@@ -40,9 +48,12 @@ impl Plugin for ReactorUiPlugin {
 			.with_system(calculate_cost_text)
 			.with_system(update_reactor_size)
 			.with_system(update_economy_text)
+			.with_system(update_target_text)
+			.with_system(update_cash_flow_text)
 			.with_system(update_button_cost_sprites)
 			.with_system(button_cost)
-			//.with_system(button_control_rod)
+			.with_system(hotkey_cost)
+			.with_system(vent)
 			.with_system(button_standby)
 		)
 		;
@@ -50,7 +61,9 @@ impl Plugin for ReactorUiPlugin {
 }
 
 // ---------- RESOURCES ----------
-const COST_SCALING: f32 = 1.0;
+// Reactor before any Bigger Vessel upgrades, it grows around its centre
+pub const REACTOR_CENTER: Vec2 = Vec2::new(120.0, 136.0);
+pub const REACTOR_SIZE: Vec2 = Vec2::new(840.0, 460.0);
 
 // ---------- COMPONENTS ----------
 #[derive(Component)]
@@ -74,6 +87,9 @@ struct PowerText;
 #[derive(Component)]
 struct TargetText;
 
+#[derive(Component)]
+struct CashFlowText;
+
 #[derive(Component)]
 struct ButtonHovered {
 	hovered_button: Option<ButtonType>
@@ -117,20 +133,12 @@ fn generate_button_standby (
 		.spawn_bundle(NodeBundle {
 			style: Style {
 				position_type: PositionType::Absolute,
-				position: UiRect {
-					// Screen width, sprite width, x translation
-					left: Val::Px(1600.0 / 2.0 - 100.0 / 2.0 + 632.5),
-					// Screen height, sprite height, y translation
-					bottom: Val::Px(900.0 / 2.0 - 100.0 / 2.0 - 363.8), 
-					..default()
-				},
-				// Sprite width and height
-				size: Size::new(Val::Px(100.0), Val::Px(100.0)),
 				..default()
 			},
 			color: INVISIBLE.into(),
 			..default()
 		})
+		.insert(WorldAnchor::new(Vec2::new(632.5, -363.8), Vec2::new(100.0, 100.0)))
 		.insert(Name::new("Standby Node"))
 		.insert(ReactorUi)
 		.with_children(|parent| {
@@ -177,16 +185,18 @@ fn generate_monitor (
 fn generate_reactor (
 	mut commands: Commands,
 	asset_server: Res<AssetServer>,
+	upgrades_query: Query<&Upgrades>,
 ) {
-	let width = 840.0; 
-	let height = 460.0;
+	let upgrades = upgrades_query.single();
+	let width = REACTOR_SIZE.x * upgrades.size_scale();
+	let height = REACTOR_SIZE.y * upgrades.size_scale();
 	commands
 		.spawn_bundle(SpriteBundle {
 			texture: asset_server.load("test_square.png"),
 			visibility: Visibility { is_visible: false },
 			// 100.0 is sprite width and height (check update_reactor_size)
 			transform: Transform::from_scale(Vec3::new(width / 100.0 , height / 100.0, 0.0))
-				.with_translation(Vec3::new(120.0, 136.0, 300.0)),
+				.with_translation(REACTOR_CENTER.extend(300.0)),
 			..default()
 	})    
 	.insert(Name::new("Reactor"))
@@ -201,28 +211,20 @@ fn generate_reactor (
 
 fn generate_text (
 	mut commands: Commands,
-	asset_server: Res<AssetServer>,
+	ui_assets: Res<UiAssets>,
 ) {
-	let font = asset_server.load("fonts/PixelSplitterBold.ttf");
+	let font = ui_assets.font.clone();
 
 	commands
 	.spawn_bundle(NodeBundle {
 		style: Style {
 			position_type: PositionType::Absolute,
-			position: UiRect {
-				// Screen width, sprite width, x translation
-				left: Val::Px(1600.0 / 2.0 - 150.0 / 2.0 - 420.0),
-				// Screen height, sprite height, y translation
-				bottom: Val::Px(900.0 / 2.0 - 70.0 / 2.0 - 160.0), 
-				..default()
-			},
-			// Sprite width and height
-			size: Size::new(Val::Px(180.0), Val::Px(100.0)),
 			..default()
 		},
 		color: INVISIBLE.into(),
 		..default()
 	})
+	.insert(WorldAnchor::new(Vec2::new(-420.0, -160.0), Vec2::new(150.0, 70.0)).with_size(Vec2::new(180.0, 100.0)))
 	.insert(Name::new("Time Node"))
 	.insert(ReactorUi)
 	.with_children(|parent| {
@@ -244,20 +246,12 @@ fn generate_text (
 	.spawn_bundle(NodeBundle {
 		style: Style {
 			position_type: PositionType::Absolute,
-			position: UiRect {
-				// Screen width, sprite width, x translation
-				left: Val::Px(1600.0 / 2.0 - 150.0 / 2.0 + 70.0),
-				// Screen height, sprite height, y translation
-				bottom: Val::Px(900.0 / 2.0 - 70.0 / 2.0 - 160.0), 
-				..default()
-			},
-			// Sprite width and height
-			size: Size::new(Val::Px(150.0), Val::Px(70.0)),
 			..default()
 		},
 		color: INVISIBLE.into(),
 		..default()
 	})
+	.insert(WorldAnchor::new(Vec2::new(70.0, -160.0), Vec2::new(150.0, 70.0)))
 	.insert(Name::new("Power Node"))
 	.insert(ReactorUi)
 	.with_children(|parent| {
@@ -279,20 +273,12 @@ fn generate_text (
 	.spawn_bundle(NodeBundle {
 		style: Style {
 			position_type: PositionType::Absolute,
-			position: UiRect {
-				// Screen width, sprite width, x translation
-				left: Val::Px(1600.0 / 2.0 - 150.0 / 2.0 + 615.0),
-				// Screen height, sprite height, y translation
-				bottom: Val::Px(900.0 / 2.0 - 70.0 / 2.0 - 160.0), 
-				..default()
-			},
-			// Sprite width and height
-			size: Size::new(Val::Px(150.0), Val::Px(70.0)),
 			..default()
 		},
 		color: INVISIBLE.into(),
 		..default()
 	})
+	.insert(WorldAnchor::new(Vec2::new(615.0, -160.0), Vec2::new(150.0, 70.0)))
 	.insert(Name::new("Target Node"))
 	.insert(ReactorUi)
 	.with_children(|parent| {
@@ -309,6 +295,33 @@ fn generate_text (
 		.insert(TargetText)
 		;
 	});
+
+	commands
+	.spawn_bundle(NodeBundle {
+		style: Style {
+			position_type: PositionType::Absolute,
+			..default()
+		},
+		color: INVISIBLE.into(),
+		..default()
+	})
+	.insert(WorldAnchor::new(Vec2::new(615.0, -375.0), Vec2::new(300.0, 70.0)))
+	.insert(Name::new("Cash Flow Node"))
+	.insert(ReactorUi)
+	.with_children(|parent| {
+		parent
+			.spawn_bundle(TextBundle::from_section(
+				"",
+				TextStyle {
+					font: font.clone(),
+					font_size: 16.0,
+					color: Color::rgb(0.0, 0.3, 0.0),
+				},
+			),
+		)
+		.insert(CashFlowText)
+		;
+	});
 }
 
 pub fn generate_cost_buttons (
@@ -368,20 +381,12 @@ fn generate_cost_button (
 		.spawn_bundle(NodeBundle {
 			style: Style {
 				position_type: PositionType::Absolute,
-				position: UiRect {
-					// Screen width, sprite width, x translation
-					left: Val::Px(1600.0 / 2.0 - 384.0 / 2.0 - 440.0),
-					// Screen height, sprite height, y translation
-					bottom: Val::Px(900.0 / 2.0 - 84.0 / 2.0 + height), 
-					..default()
-				},
-				// Sprite width and height
-				size: Size::new(Val::Px(300.0), Val::Px(84.0)),
 				..default()
 			},
 			color: INVISIBLE.into(),
 			..default()
 		})
+		.insert(WorldAnchor::new(Vec2::new(-440.0, height), Vec2::new(384.0, 84.0)).with_size(Vec2::new(300.0, 84.0)))
 		.insert(ReactorUi)
 		.with_children(|parent| {
 
@@ -428,15 +433,16 @@ fn generate_cost_button (
 fn generate_cost_button_sprites (
 	mut commands: Commands,
 	spritesheet: Res<SpriteSheets>,
-    unlocked_query: Query<&Unlocked>,
+    research_query: Query<&Research>,
 ) {
-    let unlocked = unlocked_query.single();
-    
-	generate_cost_button_sprite(&mut commands, &spritesheet, SpriteType::Molecule(MoleculeType::Red), Vec3::new(-671.5, 320.0, 999.9), ButtonType::MoleculeButton(MoleculeType::Red), unlocked.red_unlocked);
-	generate_cost_button_sprite(&mut commands, &spritesheet, SpriteType::Molecule(MoleculeType::Blue), Vec3::new(-671.5, 228.0, 999.9), ButtonType::MoleculeButton(MoleculeType::Blue), unlocked.blue_unlocked);
-	generate_cost_button_sprite(&mut commands, &spritesheet, SpriteType::Molecule(MoleculeType::Orange), Vec3::new(-671.5, 136.0, 999.9), ButtonType::MoleculeButton(MoleculeType::Orange), unlocked.orange_unlocked);
-	generate_cost_button_sprite(&mut commands, &spritesheet, SpriteType::Molecule(MoleculeType::Purple), Vec3::new(-671.5, 44.0, 999.9), ButtonType::MoleculeButton(MoleculeType::Purple), unlocked.purple_unlocked);
-	generate_cost_button_sprite(&mut commands, &spritesheet, SpriteType::Molecule(MoleculeType::Gray), Vec3::new(-671.5, -48.0, 999.9), ButtonType::MoleculeButton(MoleculeType::Gray), unlocked.gray_unlocked);
+    let research = research_query.single();
+
+	// Just under the camera so their symbols still fit above them
+	generate_cost_button_sprite(&mut commands, &spritesheet, SpriteType::Molecule(MoleculeType::Red), Vec3::new(-671.5, 320.0, 999.8), ButtonType::MoleculeButton(MoleculeType::Red), research.molecule_unlocked(MoleculeType::Red));
+	generate_cost_button_sprite(&mut commands, &spritesheet, SpriteType::Molecule(MoleculeType::Blue), Vec3::new(-671.5, 228.0, 999.8), ButtonType::MoleculeButton(MoleculeType::Blue), research.molecule_unlocked(MoleculeType::Blue));
+	generate_cost_button_sprite(&mut commands, &spritesheet, SpriteType::Molecule(MoleculeType::Orange), Vec3::new(-671.5, 136.0, 999.8), ButtonType::MoleculeButton(MoleculeType::Orange), research.molecule_unlocked(MoleculeType::Orange));
+	generate_cost_button_sprite(&mut commands, &spritesheet, SpriteType::Molecule(MoleculeType::Purple), Vec3::new(-671.5, 44.0, 999.8), ButtonType::MoleculeButton(MoleculeType::Purple), research.molecule_unlocked(MoleculeType::Purple));
+	generate_cost_button_sprite(&mut commands, &spritesheet, SpriteType::Molecule(MoleculeType::Gray), Vec3::new(-671.5, -48.0, 999.8), ButtonType::MoleculeButton(MoleculeType::Gray), research.molecule_unlocked(MoleculeType::Gray));
 }
 
 fn generate_cost_button_sprite (
@@ -447,6 +453,7 @@ fn generate_cost_button_sprite (
 	button_type: ButtonType,
     unlocked: bool,
 ) -> Entity {
+	let ButtonType::MoleculeButton(molecule_type) = button_type;
 	let sprite = spawn_spritesheet_sprite(
 		&mut commands,
 		&spritesheet,
@@ -462,6 +469,10 @@ fn generate_cost_button_sprite (
 	.insert(ReactorUi)
 	.insert(CostButtonSprites)
 	.insert(button_type)
+	.insert(MoleculeSymbol {
+		molecule_type,
+		size: 64.0,
+	})
 	.id()
 }
 
@@ -470,6 +481,7 @@ fn update_economy_text (
 	mut time_text_query: Query<&mut Text, (With<TimeText>, Without<PowerText>)>,
 	mut power_text_query: Query<&mut Text, (With<PowerText>, Without<TimeText>)>,
     godmode_query: Query<&GodMode>,
+	locale: Res<Locale>,
 ) {
 	let (_, time, power) = economy_query.single();
     let godmode = godmode_query.single();
@@ -477,7 +489,7 @@ fn update_economy_text (
 	let mut time_text = time_text_query.single_mut();
 	let current_time = ((1.0 - time.countdown.percent_left()) * 8.0 + 9.0).round();
     if godmode.0 {
-        time_text.sections[0].value = format!("Chill");
+        time_text.sections[0].value = locale.get("reactor.chill").to_string();
     } else {
 	    time_text.sections[0].value = format!("{current_time:.0}:00");
     }
@@ -504,120 +516,138 @@ fn update_economy_text (
 	}
 }
 
+// Target changes from day to day through the campaign
+fn update_target_text (
+	economy_query: Query<(&Economy, &Power), Changed<Power>>,
+	mut target_text_query: Query<&mut Text, With<TargetText>>,
+) {
+	for (_, power) in economy_query.iter() {
+		let mut target_text = target_text_query.single_mut();
+		let target = power.target_power / 1000.0;
+		target_text.sections[0].value = format!("{target:.1}k");
+	}
+}
+
+// Income against upkeep, per second
+fn update_cash_flow_text (
+	economy_query: Query<(&Economy, &CashFlow)>,
+	locale: Res<Locale>,
+	mut cash_flow_text_query: Query<&mut Text, With<CashFlowText>>,
+) {
+	let (_, cash_flow) = economy_query.single();
+	let mut cash_flow_text = cash_flow_text_query.single_mut();
+
+	cash_flow_text.sections[0].value = locale.format("reactor.cash_flow", &[
+		("income", &format!("{:+.1}", cash_flow.income)),
+		("expenses", &format!("{:.1}", -cash_flow.expenses())),
+		("net", &format!("{:+.1}", cash_flow.net())),
+		("reactor", &format!("{:.1}", cash_flow.reactor_upkeep)),
+		("molecules", &format!("{:.1}", cash_flow.molecule_upkeep)),
+		("equipment", &format!("{:.1}", cash_flow.equipment_upkeep)),
+	]);
+}
 
 fn calculate_cost_text (
 	cost_query: Query<&Cost>,
+	market_query: Query<&Market>,
 	mut boi_query: Query<(&mut Text, &TextType)>,
-    unlocked_query: Query<&Unlocked>,
+    research_query: Query<&Research>,
     godmode_query: Query<&GodMode>,
+	campaign_query: Query<&Campaign>,
 ) {
-    let unlocked = unlocked_query.single();
+    let research = research_query.single();
     let godmode = godmode_query.single();
+	let market = market_query.single();
+	let campaign = campaign_query.single();
 
 	let cost = cost_query.single();
-	
-	let red_cost = cost.red_cost;
-	let blue_cost = cost.blue_cost;
-    let orange_cost = cost.orange_cost;
-    let purple_cost = cost.purple_cost;
-    let gray_cost = cost.gray_cost;
 
 	for (text, text_type) in boi_query.iter_mut() {
-		match text_type {
-            // Red and blue always unlocked so unlocked check not necessary
-			TextType::RedboiText => if godmode.0 {
-                update_button_cost_text(0.0, text)
-            } else if unlocked.get_unlocked(MoleculeType::Red) == &true {
-                update_button_cost_text(red_cost, text)
-            } else {
-                update_button_cost_text(999000.0, text)
-            },
-			TextType::BlueboiText => if godmode.0 {
-                update_button_cost_text(0.0, text)
-            } else if unlocked.get_unlocked(MoleculeType::Blue) == &true {
-                update_button_cost_text(blue_cost, text)
-            } else {
-                update_button_cost_text(999000.0, text)
-            },
-            TextType::OrangeboiText => if godmode.0 {
-                update_button_cost_text(0.0, text)
-            } else if unlocked.get_unlocked(MoleculeType::Orange) == &true {
-                update_button_cost_text(orange_cost, text)
-            } else {
-                update_button_cost_text(999000.0, text)
-            },
-            TextType::PurpleboiText => if godmode.0 {
-                update_button_cost_text(0.0, text)
-            } else if unlocked.get_unlocked(MoleculeType::Purple) == &true {
-                update_button_cost_text(purple_cost, text)
-            } else {
-                update_button_cost_text(999000.0, text)
-            },
-            TextType::GrayboiText => if godmode.0 {
-                update_button_cost_text(0.0, text)
-            } else if unlocked.get_unlocked(MoleculeType::Gray) == &true {
-                update_button_cost_text(gray_cost, text)
-            } else {
-                update_button_cost_text(999000.0, text)
-            },
+		let molecule_type = match text_type {
+			TextType::RedboiText => MoleculeType::Red,
+			TextType::BlueboiText => MoleculeType::Blue,
+			TextType::OrangeboiText => MoleculeType::Orange,
+			TextType::PurpleboiText => MoleculeType::Purple,
+			TextType::GrayboiText => MoleculeType::Gray,
+		};
+
+		if godmode.0 {
+			update_button_cost_text(0.0, None, text)
+		} else if research.molecule_unlocked(molecule_type) && !campaign.is_banned(molecule_type) {
+			update_button_cost_text(*cost.get_cost(molecule_type), Some(market.get_entry(molecule_type).trend), text)
+		} else {
+			update_button_cost_text(999000.0, None, text)
 		}
 	}
 }
 
 fn update_button_cost_text (
 	cost: f32,
+	trend: Option<Trend>,
 	mut text: Mut<Text>,
 ) {
+	// Locked and free molecules have no market to show
+	let arrow = match trend {
+		Some(trend) => trend.arrow(),
+		None => "",
+	};
 
 	if cost < 10.0 {
-		text.sections[0].value = format!("                    {cost:.2}");
+		text.sections[0].value = format!("                    {cost:.2}{arrow}");
 	}
 	else if cost < 1000.0 {
-		text.sections[0].value = format!("                    {cost:.0}");
+		text.sections[0].value = format!("                    {cost:.0}{arrow}");
 	}
 	else if cost < 100_000.0 {
 		let kilo_cost = cost / 1000.0;
-		text.sections[0].value = format!("                    {kilo_cost:.2}k");
+		text.sections[0].value = format!("                    {kilo_cost:.2}k{arrow}");
 	}
     else if cost < 1000_000.0 {
 		let kilo_cost = cost / 1000.0;
-		text.sections[0].value = format!("                    {kilo_cost:.0}k");
+		text.sections[0].value = format!("                    {kilo_cost:.0}k{arrow}");
 	}
 }
 
 fn update_button_cost_sprites (
 	time: Res<Time>,
+	speed: Res<GameSpeed>,
 	mut animation_timer_query: Query<(&AnimationTimer, &mut BasicCountdown)>,
 	mut button_monitor_query: Query<&mut ButtonHovered>,
 	mut button_type_query: Query<(&ButtonType, &mut TextureAtlasSprite, With<CostButtonSprites>)>,
-    unlocked_query: Query<&Unlocked>,
+    research_query: Query<&Research>,
+	injector_query: Query<&Injector>,
 ) {
 	let (_animation_timer, mut countdown) = animation_timer_query.single_mut();
 	let button_monitor = button_monitor_query.single_mut();
-    let unlocked = unlocked_query.single();
+    let research = research_query.single();
+	let injector = injector_query.single();
 
-    if countdown.0.tick(time.delta()).just_finished() {
+    if countdown.0.tick(speed.delta(&time)).just_finished() {
 	    for (button_type, mut sprite, _) in button_type_query.iter_mut() {
-            if button_monitor.hovered_button == Some(*button_type) {
+			// Armed molecule keeps animating so the player can see what they're placing
+			let armed = match button_type {
+				ButtonType::MoleculeButton(molecule_type) => injector.armed == Some(*molecule_type),
+			};
+            if button_monitor.hovered_button == Some(*button_type) || armed {
                 match button_type {
                     &ButtonType::MoleculeButton(MoleculeType::Red) => {
-                        if unlocked.red_unlocked == true {sprite.index = (sprite.index + 1) % 8;}
+                        if research.molecule_unlocked(MoleculeType::Red) {sprite.index = (sprite.index + 1) % 8;}
                         else {sprite.index = (sprite.index + 1) % 8 + 8;}
                     },
                     &ButtonType::MoleculeButton(MoleculeType::Blue) => {
-                        if unlocked.blue_unlocked == true {sprite.index = (sprite.index + 1) % 8;}
+                        if research.molecule_unlocked(MoleculeType::Blue) {sprite.index = (sprite.index + 1) % 8;}
                         else{sprite.index = (sprite.index + 1) % 8 + 8;}
                     },
                     &ButtonType::MoleculeButton(MoleculeType::Orange) => {
-                        if unlocked.orange_unlocked == true {sprite.index = (sprite.index + 1) % 8;}
+                        if research.molecule_unlocked(MoleculeType::Orange) {sprite.index = (sprite.index + 1) % 8;}
                         else{sprite.index = (sprite.index + 1) % 8 + 8;}
                     },
                     &ButtonType::MoleculeButton(MoleculeType::Purple) => {
-                        if unlocked.purple_unlocked == true {sprite.index = (sprite.index + 1) % 8;}
+                        if research.molecule_unlocked(MoleculeType::Purple) {sprite.index = (sprite.index + 1) % 8;}
                         else{sprite.index = (sprite.index + 1) % 8 + 8;}
                     },
                     &ButtonType::MoleculeButton(MoleculeType::Gray) => {
-                        if unlocked.gray_unlocked == true {sprite.index = (sprite.index + 1) % 8;}
+                        if research.molecule_unlocked(MoleculeType::Gray) {sprite.index = (sprite.index + 1) % 8;}
                         else{sprite.index = (sprite.index + 1) % 8 + 8;}
                     },
                 }
@@ -625,23 +655,23 @@ fn update_button_cost_sprites (
             else {
                 match button_type {
                     &ButtonType::MoleculeButton(MoleculeType::Red) => {
-                        if unlocked.red_unlocked == true {sprite.index = 0;}
+                        if research.molecule_unlocked(MoleculeType::Red) {sprite.index = 0;}
                         else {sprite.index = 8;}
                     },
                     &ButtonType::MoleculeButton(MoleculeType::Blue) => {
-                        if unlocked.blue_unlocked == true {sprite.index = 0;}
+                        if research.molecule_unlocked(MoleculeType::Blue) {sprite.index = 0;}
                         else{sprite.index = 8;}
                     },
                     &ButtonType::MoleculeButton(MoleculeType::Orange) => {
-                        if unlocked.orange_unlocked == true {sprite.index = 0;}
+                        if research.molecule_unlocked(MoleculeType::Orange) {sprite.index = 0;}
                         else{sprite.index = 8;}
                     },
                     &ButtonType::MoleculeButton(MoleculeType::Purple) => {
-                        if unlocked.purple_unlocked == true {sprite.index = 0;}
+                        if research.molecule_unlocked(MoleculeType::Purple) {sprite.index = 0;}
                         else{sprite.index = 8;}
                     },
                     &ButtonType::MoleculeButton(MoleculeType::Gray) => {
-                        if unlocked.gray_unlocked == true {sprite.index = 0;}
+                        if research.molecule_unlocked(MoleculeType::Gray) {sprite.index = 0;}
                         else{sprite.index = 8;}
                     },
                 }
@@ -665,26 +695,20 @@ fn update_reactor_size (
 // Lmao yeah, why is it even named button cost? -iq
 // All the button functions are called "button_function"
 // This one is the button that displays the cost of molecules
-// And calls their spawn function
+// And arms the injector with that molecule
 fn button_cost (
-	mut commands: Commands,
-	spritesheet: Res<SpriteSheets>,
-	mut cost_query: Query<&mut Cost>,
 	mut interaction_query: Query<(&ButtonType, &Children, &Interaction), Changed<Interaction>>,
-	mut molecule_list_query: Query<&MoleculeList>,
-	reactor_query: Query<(&Reactor, &Dimensions, &Transform)>,
-	mut economy_query: Query<(&Economy, &mut Power)>,
 	mut button_image_query: Query<&mut UiImage>,
 	ui_assets: Res<UiAssets>,
 	mut button_monitor_query: Query<&mut ButtonHovered>,
-    unlocked_query: Query<&Unlocked>,
-    godmode_query: Query<&GodMode>,
+	mut injector_query: Query<&mut Injector>,
+    research_query: Query<&Research>,
+	campaign_query: Query<&Campaign>,
 ) {
-	let (_economy, mut power) = economy_query.single_mut();
-	let mut cost = cost_query.single_mut();
 	let mut button_monitor = button_monitor_query.single_mut();
-    let unlocked = unlocked_query.single();
-    let godmode = godmode_query.single();
+	let mut injector = injector_query.single_mut();
+    let research = research_query.single();
+	let campaign = campaign_query.single();
 
 	for (button_type, children, interaction) in interaction_query.iter_mut() {
 		let child = children.iter().next().unwrap();
@@ -695,13 +719,13 @@ fn button_cost (
 				image.0 = ui_assets.button_hovered.clone();
 				match button_type {
 					ButtonType::MoleculeButton(molecule_type) => {
-						let molecule_cost = cost.get_cost_mut(*molecule_type);
-                        if unlocked.get_unlocked(*molecule_type) == &true {
-                            if power.current_power > *molecule_cost || godmode.0{
-                                add_molecule(*molecule_type, &mut commands, &spritesheet, &mut molecule_list_query, &reactor_query);
-                                power.current_power -= *molecule_cost;
-                                *molecule_cost *= COST_SCALING;
-                            };
+                        if research.molecule_unlocked(*molecule_type) && !campaign.is_banned(*molecule_type) {
+							// Molecule is placed and launched by click-dragging in the reactor
+							if injector.armed == Some(*molecule_type) {
+								injector.armed = None;
+							} else {
+								injector.armed = Some(*molecule_type);
+							}
                         }
 					}
 				}
@@ -720,14 +744,37 @@ fn button_cost (
 	}
 }
 
-// Change to V for vent???
-// sus
-fn button_control_rod (
+// Same as clicking the cost button
+fn hotkey_cost (
+	actions: Actions,
+	mut injector_query: Query<&mut Injector>,
+    research_query: Query<&Research>,
+	campaign_query: Query<&Campaign>,
+) {
+	let mut injector = injector_query.single_mut();
+    let research = research_query.single();
+	let campaign = campaign_query.single();
+
+	for action in INPUT_ACTIONS {
+		if let InputAction::SpawnMolecule(molecule_type) = action {
+			if actions.just_pressed(action) && research.molecule_unlocked(molecule_type) && !campaign.is_banned(molecule_type) {
+				if injector.armed == Some(molecule_type) {
+					injector.armed = None;
+				} else {
+					injector.armed = Some(molecule_type);
+				}
+			}
+		}
+	}
+}
+
+// Drops control rods in, emptying the reactor
+fn vent (
 	commands: Commands,
-	keyboard: Res<Input<KeyCode>>,
+	actions: Actions,
 	entities: Query<Entity, With<Molecule>>,
 ) {
-	if keyboard.just_pressed(KeyCode::Escape) {
+	if actions.just_pressed(InputAction::Vent) {
 		add_control_rod(commands, entities);
 	}
 }
@@ -0,0 +1,460 @@
+use bevy::prelude::*;
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::{despawn_screen, UiAssets},
+	enums::{MoleculeType, ReactionType, WallMaterial},
+	shop::UpgradeKind,
+	locale::Locale,
+};
+
+// ---------- PLUGINS ----------
+pub struct ResearchPlugin;
+
+impl Plugin for ResearchPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_startup_system(generate_research)
+		.add_system_set(SystemSet::on_enter(GameState::Research)
+			.with_system(load_research)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Research)
+			.with_system(button_research_node)
+			.with_system(button_exit_research)
+			.with_system(update_research_nodes)
+		)
+		.add_system_set(SystemSet::on_exit(GameState::Research)
+			.with_system(despawn_screen::<ResearchUi>)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+// Points for every molecule synthesized
+pub const RESEARCH_PER_PRODUCT: f32 = 0.2;
+// Extra points the first time a molecule nobody has researched yet turns up, once per molecule
+pub const DISCOVERY_POINTS: f32 = 5.0;
+
+const COMPLETE_COLOR: Color = Color::rgb(0.2, 0.55, 0.2);
+const AVAILABLE_COLOR: Color = Color::rgb(0.75, 0.75, 0.75);
+const LOCKED_COLOR: Color = Color::rgb(0.3, 0.3, 0.3);
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReactorPart {
+	Collector,
+	FieldEmitter,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ResearchEffect {
+	// Molecule can be bought
+	Molecule(MoleculeType),
+	// Reaction happens instead of the molecules bouncing off each other
+	Reaction(ReactionType),
+	Part(ReactorPart),
+	// Upgrade can be bought in the shop
+	Upgrade(UpgradeKind),
+}
+
+pub struct ResearchNode {
+	// Locale key
+	pub name: &'static str,
+	// Nodes costing nothing are known from the start
+	pub cost: f32,
+	// Indices into RESEARCH that have to be completed first
+	pub prerequisites: &'static [usize],
+	// Column on the research screen
+	pub tier: usize,
+	pub effect: ResearchEffect,
+}
+
+pub const RESEARCH: [ResearchNode; 16] = [
+	ResearchNode {
+		name: "research.red_stock",
+		cost: 0.0,
+		prerequisites: &[],
+		tier: 0,
+		effect: ResearchEffect::Molecule(MoleculeType::Red),
+	},
+	ResearchNode {
+		name: "research.blue_stock",
+		cost: 0.0,
+		prerequisites: &[],
+		tier: 0,
+		effect: ResearchEffect::Molecule(MoleculeType::Blue),
+	},
+	ResearchNode {
+		name: "research.red_blue_fusion",
+		cost: 0.0,
+		prerequisites: &[0, 1],
+		tier: 1,
+		effect: ResearchEffect::Reaction(ReactionType::RedBlue),
+	},
+	ResearchNode {
+		name: "research.blue_splitting",
+		cost: 0.0,
+		prerequisites: &[1],
+		tier: 1,
+		effect: ResearchEffect::Reaction(ReactionType::BlueBlue),
+	},
+	ResearchNode {
+		name: "research.gray_catalysis",
+		cost: 0.0,
+		prerequisites: &[0],
+		tier: 1,
+		effect: ResearchEffect::Reaction(ReactionType::GrayRed),
+	},
+	ResearchNode {
+		name: "research.purple_stock",
+		cost: 10.0,
+		prerequisites: &[2],
+		tier: 2,
+		effect: ResearchEffect::Molecule(MoleculeType::Purple),
+	},
+	ResearchNode {
+		name: "research.gray_stock",
+		cost: 10.0,
+		prerequisites: &[3],
+		tier: 2,
+		effect: ResearchEffect::Molecule(MoleculeType::Gray),
+	},
+	ResearchNode {
+		name: "research.collector_ports",
+		cost: 15.0,
+		prerequisites: &[2],
+		tier: 2,
+		effect: ResearchEffect::Part(ReactorPart::Collector),
+	},
+	ResearchNode {
+		name: "research.ceramic_walls",
+		cost: 15.0,
+		prerequisites: &[3],
+		tier: 2,
+		effect: ResearchEffect::Upgrade(UpgradeKind::Walls(WallMaterial::Ceramic)),
+	},
+	ResearchNode {
+		name: "research.purple_transmutation",
+		cost: 20.0,
+		prerequisites: &[5, 6],
+		tier: 3,
+		effect: ResearchEffect::Reaction(ReactionType::GrayPurple),
+	},
+	ResearchNode {
+		name: "research.field_emitters",
+		cost: 20.0,
+		prerequisites: &[7],
+		tier: 3,
+		effect: ResearchEffect::Part(ReactorPart::FieldEmitter),
+	},
+	ResearchNode {
+		name: "research.tungsten_walls",
+		cost: 30.0,
+		prerequisites: &[8],
+		tier: 3,
+		effect: ResearchEffect::Upgrade(UpgradeKind::Walls(WallMaterial::Tungsten)),
+	},
+	ResearchNode {
+		name: "research.orange_stock",
+		cost: 25.0,
+		prerequisites: &[9],
+		tier: 4,
+		effect: ResearchEffect::Molecule(MoleculeType::Orange),
+	},
+	ResearchNode {
+		name: "research.graphene_walls",
+		cost: 50.0,
+		prerequisites: &[11],
+		tier: 4,
+		effect: ResearchEffect::Upgrade(UpgradeKind::Walls(WallMaterial::Graphene)),
+	},
+	ResearchNode {
+		name: "research.orange_fission",
+		cost: 30.0,
+		prerequisites: &[12],
+		tier: 5,
+		effect: ResearchEffect::Reaction(ReactionType::OrangeOrange),
+	},
+	ResearchNode {
+		name: "research.orange_recycling",
+		cost: 30.0,
+		prerequisites: &[12],
+		tier: 5,
+		effect: ResearchEffect::Reaction(ReactionType::GrayOrange),
+	},
+];
+
+// ---------- COMPONENTS ----------
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NodeState {
+	Complete,
+	Available,
+	Locked,
+}
+
+// Research progress, kept between days
+#[derive(Component)]
+pub struct Research {
+	pub points: f32,
+	// Whether each entry in RESEARCH is done
+	pub completed: [bool; RESEARCH.len()],
+	// Molecules that have already paid out DISCOVERY_POINTS
+	pub discovered: Vec<MoleculeType>,
+}
+
+impl Research {
+	pub fn node_state(&self, index: usize) -> NodeState {
+		if self.completed[index] {
+			NodeState::Complete
+		} else if RESEARCH[index].prerequisites.iter().all(|prerequisite| self.completed[*prerequisite]) {
+			NodeState::Available
+		} else {
+			NodeState::Locked
+		}
+	}
+
+	// Anything no node covers is always allowed
+	pub fn is_unlocked(&self, effect: ResearchEffect) -> bool {
+		RESEARCH.iter()
+			.enumerate()
+			.filter(|(_, node)| node.effect == effect)
+			.all(|(index, _)| self.completed[index])
+	}
+
+	pub fn molecule_unlocked(&self, molecule_type: MoleculeType) -> bool {
+		self.is_unlocked(ResearchEffect::Molecule(molecule_type))
+	}
+
+	// Marks every node with this effect done, used for rewards that skip the tree
+	pub fn grant(&mut self, effect: ResearchEffect) {
+		for (index, node) in RESEARCH.iter().enumerate() {
+			if node.effect == effect {
+				self.completed[index] = true;
+			}
+		}
+	}
+
+	pub fn grant_all(&mut self) {
+		self.completed = [true; RESEARCH.len()];
+	}
+
+	// Points for the products of a successful reaction
+	pub fn record_synthesis(&mut self, products: &[MoleculeType]) {
+		for product in products {
+			self.points += RESEARCH_PER_PRODUCT;
+			if !self.molecule_unlocked(*product) && !self.discovered.contains(product) {
+				self.points += DISCOVERY_POINTS;
+				self.discovered.push(*product);
+			}
+		}
+	}
+}
+
+#[derive(Component)]
+struct ResearchUi;
+
+#[derive(Component)]
+struct ResearchNodeButton(usize);
+
+#[derive(Component)]
+struct ResearchPointsText;
+
+#[derive(Component)]
+struct ResearchExitButton;
+
+// ---------- SYSTEMS ----------
+fn generate_research (
+	mut commands: Commands,
+) {
+	let mut completed = [false; RESEARCH.len()];
+	for (index, node) in RESEARCH.iter().enumerate() {
+		completed[index] = node.cost == 0.0;
+	}
+
+	commands
+		.spawn()
+		.insert(Research {
+			points: 0.0,
+			completed,
+			discovered: Vec::new(),
+		})
+		.insert(Name::new("Research"));
+}
+
+fn load_research (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
+) {
+	let text_style = TextStyle {
+		font: ui_assets.font.clone(),
+		font_size: 20.0,
+		color: Color::BLACK,
+	};
+
+	let tiers = RESEARCH.iter().map(|node| node.tier).max().unwrap_or(0) + 1;
+
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				margin: UiRect::all(Val::Auto),
+				flex_direction: FlexDirection::ColumnReverse,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(ResearchUi)
+		.insert(Name::new("Research UI"))
+		.with_children(|parent| {
+			parent
+				.spawn_bundle(TextBundle::from_section(
+					"",
+					TextStyle {
+						font_size: 40.0,
+						color: ui_assets.text_color,
+						..text_style.clone()
+					},
+				))
+				.insert(ResearchPointsText);
+
+			// One column per tier, prerequisites always sit in earlier columns
+			parent
+				.spawn_bundle(NodeBundle {
+					style: Style {
+						flex_direction: FlexDirection::Row,
+						align_items: AlignItems::FlexStart,
+						..default()
+					},
+					color: INVISIBLE.into(),
+					..default()
+				})
+				.with_children(|parent| {
+					for tier in 0..tiers {
+						parent
+							.spawn_bundle(NodeBundle {
+								style: Style {
+									flex_direction: FlexDirection::ColumnReverse,
+									justify_content: JustifyContent::FlexEnd,
+									..default()
+								},
+								color: INVISIBLE.into(),
+								..default()
+							})
+							.with_children(|parent| {
+								for (index, node) in RESEARCH.iter().enumerate().filter(|(_, node)| node.tier == tier) {
+
+									// Research Node Button
+									parent
+										.spawn_bundle(ButtonBundle {
+											style: Style {
+												size: Size::new(Val::Px(220.0), Val::Px(70.0)),
+												margin: UiRect::all(Val::Px(6.0)),
+												justify_content: JustifyContent::Center,
+												align_items: AlignItems::Center,
+												..default()
+											},
+											color: LOCKED_COLOR.into(),
+											..default()
+										})
+										.insert(ResearchNodeButton(index))
+										.with_children(|parent| {
+											parent.spawn_bundle(TextBundle::from_section(
+												locale.format("research.node", &[("name", &locale.get(node.name)), ("cost", &format!("{:.0}", node.cost))]),
+												text_style.clone(),
+											));
+										});
+								}
+							});
+					}
+				});
+
+			// Back Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: Style {
+						size: Size::new(Val::Px(250.0), Val::Px(55.0)),
+						margin: UiRect::all(Val::Px(20.0)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					color: ui_assets.button_color.into(),
+					image: ui_assets.button_idle.clone().into(),
+					..default()
+				})
+				.insert(ResearchExitButton)
+				.with_children(|parent| {
+					parent.spawn_bundle(TextBundle::from_section(
+						locale.get("menu.back"),
+						TextStyle {
+							font_size: 40.0,
+							color: ui_assets.text_color,
+							..text_style.clone()
+						},
+					));
+				});
+		});
+}
+
+fn update_research_nodes (
+	research_query: Query<&Research>,
+	mut button_query: Query<(&ResearchNodeButton, &mut UiColor)>,
+	mut points_text_query: Query<&mut Text, With<ResearchPointsText>>,
+	locale: Res<Locale>,
+) {
+	let research = research_query.single();
+
+	for mut text in points_text_query.iter_mut() {
+		text.sections[0].value = locale.format("research.points", &[("points", &format!("{:.1}", research.points))]);
+	}
+
+	for (button, mut color) in button_query.iter_mut() {
+		*color = match research.node_state(button.0) {
+			NodeState::Complete => COMPLETE_COLOR,
+			NodeState::Available => AVAILABLE_COLOR,
+			NodeState::Locked => LOCKED_COLOR,
+		}.into();
+	}
+}
+
+fn button_research_node (
+	interaction_query: Query<(&ResearchNodeButton, &Interaction), Changed<Interaction>>,
+	mut research_query: Query<&mut Research>,
+) {
+	let mut research = research_query.single_mut();
+
+	for (button, interaction) in interaction_query.iter() {
+		let cost = RESEARCH[button.0].cost;
+		if *interaction == Interaction::Clicked
+		&& research.node_state(button.0) == NodeState::Available
+		&& research.points >= cost {
+			research.points -= cost;
+			research.completed[button.0] = true;
+		}
+	}
+}
+
+fn button_exit_research (
+	interaction_query: Query<(&ResearchExitButton, &Interaction), Changed<Interaction>>,
+	mut image_query: Query<&mut UiImage, With<ResearchExitButton>>,
+	ui_assets: Res<UiAssets>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, interaction) in interaction_query.iter() {
+		let mut image = image_query.single_mut();
+
+		match interaction {
+			Interaction::Clicked => {
+				state.pop().expect("Failed to change states");
+			}
+			Interaction::Hovered => {
+				image.0 = ui_assets.button_hovered.clone();
+			}
+			Interaction::None => {
+				image.0 = ui_assets.button_idle.clone();
+			}
+		}
+	}
+}
@@ -0,0 +1,311 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::UiAssets,
+	camera::{PanCamera, cursor_world_position},
+	reactor::Molecule,
+	reactor_ui::ReactorUi,
+	economy::{Economy, Power, Market},
+	collector::Inventory,
+	injector::{Injector, InjectorLabel},
+	fields::{FieldPlacer, FieldEmitter, EMITTER_PICK_RADIUS},
+	enums::MoleculeType,
+	layout::WorldAnchor,
+	locale::Locale,
+};
+
+// ---------- PLUGINS ----------
+pub struct SalesPlugin;
+
+impl Plugin for SalesPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_startup_system(generate_ledger)
+		.add_system_set(SystemSet::on_enter(GameState::Reactor)
+			.with_system(generate_sell_buttons)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(sell_from_reactor.before(InjectorLabel))
+			.with_system(button_sell)
+			.with_system(update_sell_text)
+			.with_system(update_ledger_text)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+// Fraction of the market price the market keeps when buying molecules back
+pub const SELL_SPREAD: f32 = 0.3;
+
+// Sales the ledger remembers, older ones drop off the front
+const LEDGER_LENGTH: usize = 200;
+// Sales listed under the reactor, newest first
+const RECENT_SALES: usize = 3;
+
+// ---------- COMPONENTS ----------
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SaleSource {
+	// Picked out of the vessel by hand
+	Reactor,
+	// Harvested by a collector port set to sell
+	Collector,
+	// Sold from the stored inventory
+	Inventory,
+}
+
+impl SaleSource {
+	pub fn name<'a>(&self, locale: &'a Locale) -> &'a str {
+		match self {
+			SaleSource::Reactor => locale.get("sales.from_reactor"),
+			SaleSource::Collector => locale.get("sales.from_collector"),
+			SaleSource::Inventory => locale.get("sales.from_inventory"),
+		}
+	}
+}
+
+pub struct Sale {
+	pub molecule_type: MoleculeType,
+	pub price: f32,
+	pub source: SaleSource,
+}
+
+// Most recent sales, kept between days
+#[derive(Component)]
+pub struct Ledger {
+	pub sales: VecDeque<Sale>,
+}
+
+#[derive(Component)]
+struct SellButton(MoleculeType);
+
+#[derive(Component)]
+struct SellText(MoleculeType);
+
+#[derive(Component)]
+struct LedgerText;
+
+// ---------- SYSTEMS ----------
+pub fn sell_price (
+	market: &Market,
+	molecule_type: MoleculeType,
+) -> f32 {
+	market.price(molecule_type) * (1.0 - SELL_SPREAD)
+}
+
+// Credits power for a molecule and records the sale
+pub fn sell_molecule (
+	molecule_type: MoleculeType,
+	source: SaleSource,
+	market: &Market,
+	power: &mut Power,
+	ledger: &mut Ledger,
+) {
+	let price = sell_price(market, molecule_type);
+	power.current_power = (power.current_power + price).clamp(0.0, 999000.0);
+	if ledger.sales.len() >= LEDGER_LENGTH {
+		ledger.sales.pop_front();
+	}
+	ledger.sales.push_back(Sale {
+		molecule_type,
+		price,
+		source,
+	});
+}
+
+fn generate_ledger (
+	mut commands: Commands,
+) {
+	commands
+		.spawn()
+		.insert(Ledger {
+			sales: VecDeque::new(),
+		})
+		.insert(Name::new("Ledger"));
+}
+
+fn generate_sell_buttons (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+) {
+	let molecule_types = [
+		MoleculeType::Red,
+		MoleculeType::Blue,
+		MoleculeType::Orange,
+		MoleculeType::Purple,
+		MoleculeType::Gray,
+	];
+
+	// Row of inventory sell buttons just under the reactor
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				justify_content: JustifyContent::SpaceAround,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(120.0, -110.0), Vec2::new(840.0, 20.0)))
+		.insert(Name::new("Sell Node"))
+		.insert(ReactorUi)
+		.with_children(|parent| {
+			for molecule_type in molecule_types {
+
+				// Sell Button
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: Style {
+							size: Size::new(Val::Px(150.0), Val::Px(20.0)),
+							justify_content: JustifyContent::Center,
+							align_items: AlignItems::Center,
+							..default()
+						},
+						color: INVISIBLE.into(),
+						..default()
+					})
+					.insert(SellButton(molecule_type))
+					.with_children(|parent| {
+						parent
+							.spawn_bundle(TextBundle::from_section(
+								"",
+								TextStyle {
+									font: ui_assets.font.clone(),
+									font_size: 16.0,
+									color: ui_assets.text_color,
+								},
+							))
+							.insert(SellText(molecule_type));
+					});
+			}
+		});
+
+	// Recent sales in the grey bar under the monitor
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(WorldAnchor::new(Vec2::new(100.0, -300.0), Vec2::new(500.0, 90.0)))
+		.insert(Name::new("Ledger Node"))
+		.insert(ReactorUi)
+		.with_children(|parent| {
+			parent
+				.spawn_bundle(TextBundle::from_section(
+					"",
+					TextStyle {
+						font: ui_assets.font.clone(),
+						font_size: 16.0,
+						color: Color::WHITE,
+					},
+				))
+				.insert(LedgerText);
+		});
+}
+
+fn update_sell_text (
+	inventory_query: Query<&Inventory>,
+	market_query: Query<&Market>,
+	locale: Res<Locale>,
+	mut text_query: Query<(&SellText, &mut Text)>,
+) {
+	let inventory = inventory_query.single();
+	let market = market_query.single();
+
+	for (sell_text, mut text) in text_query.iter_mut() {
+		let price = sell_price(market, sell_text.0);
+		text.sections[0].value = locale.format("sales.sell", &[
+			("molecule", &sell_text.0.short_name(&locale)),
+			("count", &inventory.get_count(sell_text.0)),
+			("price", &format!("{price:.1}")),
+		]);
+	}
+}
+
+fn update_ledger_text (
+	ledger_query: Query<&Ledger>,
+	locale: Res<Locale>,
+	mut text_query: Query<&mut Text, With<LedgerText>>,
+) {
+	let ledger = ledger_query.single();
+	let mut text = text_query.single_mut();
+
+	let mut lines = vec![locale.get("sales.recent").to_string()];
+	lines.extend(ledger.sales.iter().rev().take(RECENT_SALES).map(|sale| locale.format("sales.entry", &[
+		("molecule", &sale.molecule_type.name(&locale)),
+		("price", &format!("{:.1}", sale.price)),
+		("source", &sale.source.name(&locale)),
+	])));
+	text.sections[0].value = lines.join("\n");
+}
+
+fn button_sell (
+	interaction_query: Query<(&SellButton, &Interaction), Changed<Interaction>>,
+	mut inventory_query: Query<&mut Inventory>,
+	market_query: Query<&Market>,
+	mut economy_query: Query<(&Economy, &mut Power)>,
+	mut ledger_query: Query<&mut Ledger>,
+) {
+	let mut inventory = inventory_query.single_mut();
+	let market = market_query.single();
+	let (_economy, mut power) = economy_query.single_mut();
+	let mut ledger = ledger_query.single_mut();
+
+	for (sell_button, interaction) in interaction_query.iter() {
+		let count = inventory.get_count_mut(sell_button.0);
+		if *interaction == Interaction::Clicked && *count > 0 {
+			*count -= 1;
+			sell_molecule(sell_button.0, SaleSource::Inventory, market, &mut power, &mut ledger);
+		}
+	}
+}
+
+// Right clicking a molecule in the vessel sells it when no tool is in hand
+fn sell_from_reactor (
+	mut commands: Commands,
+	mouse: Res<Input<MouseButton>>,
+	windows: Res<Windows>,
+	camera_query: Query<(&Camera, &GlobalTransform), With<PanCamera>>,
+	injector_query: Query<&Injector>,
+	placer_query: Query<&FieldPlacer>,
+	emitter_query: Query<&Transform, With<FieldEmitter>>,
+	mut molecule_query: Query<(Entity, &mut Molecule, &Transform)>,
+	market_query: Query<&Market>,
+	mut economy_query: Query<(&Economy, &mut Power)>,
+	mut ledger_query: Query<&mut Ledger>,
+) {
+	if !mouse.just_pressed(MouseButton::Right) || injector_query.single().armed.is_some() || placer_query.single().armed.is_some() {
+		return;
+	}
+
+	let (camera, camera_transform) = camera_query.single();
+	let cursor = match cursor_world_position(&windows, camera, camera_transform) {
+		Some(cursor) => cursor,
+		None => return,
+	};
+	// Right clicking an emitter removes it instead
+	if emitter_query.iter().any(|transform| transform.translation.truncate().distance(cursor) <= EMITTER_PICK_RADIUS) {
+		return;
+	}
+	let market = market_query.single();
+	let (_economy, mut power) = economy_query.single_mut();
+	let mut ledger = ledger_query.single_mut();
+
+	for (entity, mut molecule, transform) in molecule_query.iter_mut() {
+		if !molecule.reacted && transform.translation.truncate().distance(cursor) <= 16.0 {
+			molecule.reacted = true;
+			commands.entity(entity).despawn_recursive();
+			sell_molecule(molecule.variant, SaleSource::Reactor, market, &mut power, &mut ledger);
+			// One molecule per click
+			break;
+		}
+	}
+}
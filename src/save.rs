@@ -0,0 +1,861 @@
+use std::{fs, path::{Path, PathBuf}, time::Duration};
+
+use bevy::{prelude::*, ecs::system::SystemParam, app::AppExit};
+use serde::{Serialize, Deserialize};
+
+use crate::{
+	GameState,
+	art::SpriteSheets,
+	reactor::{GodMode, ReactorWalls, Molecule, Velocity, MoleculeList, spawn_molecule},
+	economy::{Economy, Power, Temperature, Integrity, Clock, Market, MarketEntry},
+	enums::{MoleculeType, WallMaterial},
+	campaign::{Campaign, DayResult},
+	research::{Research, RESEARCH},
+	shop::{Upgrades, UPGRADES},
+	collector::Inventory,
+	battery::Battery,
+	loans::Debt,
+	grid::Grid,
+	settings::Settings,
+	profiles::ActiveProfile,
+};
+
+// ---------- PLUGINS ----------
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.insert_resource(LoadRequest {
+			pending: false,
+			resume: false,
+			slot: SaveSlot::Manual,
+		})
+		.insert_resource(CrashRecovery {
+			available: false,
+		})
+		.insert_resource(AutosaveTimer(Timer::from_seconds(1.0, true)))
+		.add_startup_system(check_session)
+		// Any state change is a good moment to autosave, once the new state's enter systems have run
+		.add_system_to_stage(CoreStage::PostUpdate, autosave_on_transition)
+		// Runs after anything that could have asked the app to exit this frame
+		.add_system_to_stage(CoreStage::Last, end_session)
+		// Leaving the reactor keeps the day in progress unless it just ended
+		.add_system_set(SystemSet::on_exit(GameState::Reactor)
+			.with_system(write_save)
+		)
+		.add_system_set(SystemSet::on_enter(GameState::DaySummary)
+			.with_system(write_save)
+		)
+		.add_system_set(SystemSet::on_exit(GameState::Shop)
+			.with_system(write_save)
+		)
+		.add_system_set(SystemSet::on_exit(GameState::Research)
+			.with_system(write_save)
+		)
+		.add_system_set(SystemSet::on_enter(GameState::StartMenu)
+			.with_system(capture_fresh_progress)
+		)
+		.add_system_set(SystemSet::on_enter(GameState::Lab)
+			.with_system(apply_load)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Reactor)
+			.with_system(restore_day)
+			.with_system(autosave_periodically)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+// Bump when the format changes, older saves are upgraded in migrate
+pub const SAVE_VERSION: u32 = 1;
+// Exists while the game is running, left behind if it crashes
+const LOCK_FILE: &str = "session.lock";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SaveSlot {
+	// Written when leaving the reactor, shop or research
+	Manual,
+	// Written on a timer and on every state change
+	Autosave,
+}
+
+impl SaveSlot {
+	fn file_name(&self) -> &'static str {
+		match self {
+			SaveSlot::Manual => "save.ron",
+			SaveSlot::Autosave => "autosave.ron",
+		}
+	}
+}
+
+pub const MOLECULE_TYPES: [MoleculeType; 5] = [
+	MoleculeType::Red,
+	MoleculeType::Blue,
+	MoleculeType::Orange,
+	MoleculeType::Purple,
+	MoleculeType::Gray,
+];
+
+// Set by the start menu, picked up when the Lab opens
+pub struct LoadRequest {
+	pub pending: bool,
+	// Go straight back into the saved day if there is one
+	pub resume: bool,
+	pub slot: SaveSlot,
+}
+
+// Whether the last session ended without a clean exit and left an autosave behind
+pub struct CrashRecovery {
+	pub available: bool,
+}
+
+struct AutosaveTimer(Timer);
+
+// Progression as it is at launch, put back when switching profiles
+pub struct FreshProgress(pub ProgressSave);
+
+// Day waiting to be put back into the reactor once it has been built
+struct PendingDay(DaySave);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SaveFile {
+	pub version: u32,
+	pub progress: ProgressSave,
+	pub god_mode: bool,
+	// Only present when the game was left in the middle of a day
+	#[serde(default)]
+	pub day: Option<DaySave>,
+}
+
+// Everything kept between days
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProgressSave {
+	pub campaign_day: usize,
+	pub campaign_finished: bool,
+	pub results: Vec<DayResult>,
+	pub research_points: f32,
+	pub research_completed: Vec<bool>,
+	#[serde(default)]
+	pub research_discovered: Vec<MoleculeType>,
+	pub credits: f32,
+	pub upgrade_levels: Vec<usize>,
+	pub walls: WallMaterial,
+	pub inventory: Inventory,
+	// In MOLECULE_TYPES order
+	pub market: Vec<MarketEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DaySave {
+	pub current_power: f32,
+	pub target_power: f32,
+	pub generated: f32,
+	pub current_pressure: f32,
+	pub max_pressure: f32,
+	pub current_integrity: f32,
+	pub max_integrity: f32,
+	pub day_length: f32,
+	pub clock_remaining: f32,
+	pub battery_stored: f32,
+	pub battery_discharging: bool,
+	pub debt: f32,
+	#[serde(default)]
+	pub molecules: Vec<MoleculeSave>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct MoleculeSave {
+	pub variant: MoleculeType,
+	pub position: (f32, f32),
+	pub velocity: (f32, f32),
+}
+
+// ---------- SYSTEMS ----------
+// Shared by every profile, saves themselves live in the profile's directory
+pub fn save_path (
+	file_name: &str,
+) -> PathBuf {
+	dirs::data_dir()
+		.map(|dir| dir.join("mole_rancher"))
+		.unwrap_or_default()
+		.join(file_name)
+}
+
+// Every file the game writes goes through here: saves, profiles and settings
+pub fn write_ron<T: Serialize> (
+	path: &Path,
+	value: &T,
+) {
+	if let Some(dir) = path.parent() {
+		let _ = fs::create_dir_all(dir);
+	}
+	let result = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+		.map_err(|error| error.to_string())
+		.and_then(|text| fs::write(path, text).map_err(|error| error.to_string()));
+	if let Err(error) = result {
+		warn!("Failed to write {}: {error}", path.display());
+	}
+}
+
+pub fn read_save (
+	profile: &ActiveProfile,
+	slot: SaveSlot,
+) -> Option<SaveFile> {
+	let text = fs::read_to_string(profile.dir().join(slot.file_name())).ok()?;
+	match ron::from_str::<SaveFile>(&text) {
+		Ok(save) => migrate(save),
+		Err(error) => {
+			warn!("Ignoring unreadable save: {error}");
+			None
+		}
+	}
+}
+
+// Brings older saves up to SAVE_VERSION, refuses ones from newer builds
+fn migrate (
+	save: SaveFile,
+) -> Option<SaveFile> {
+	match save.version {
+		SAVE_VERSION => Some(save),
+		version => {
+			warn!("Ignoring save with unknown version {version}");
+			None
+		}
+	}
+}
+
+pub fn write_save_file (
+	profile: &ActiveProfile,
+	slot: SaveSlot,
+	save: &SaveFile,
+) {
+	write_ron(&profile.dir().join(slot.file_name()), save);
+}
+
+// Everything a save is built from, shared by the manual and auto saves
+#[derive(SystemParam)]
+pub struct SaveSources<'w, 's> {
+	campaign_query: Query<'w, 's, &'static Campaign>,
+	research_query: Query<'w, 's, &'static Research>,
+	upgrades_query: Query<'w, 's, &'static Upgrades>,
+	walls_query: Query<'w, 's, &'static ReactorWalls>,
+	inventory_query: Query<'w, 's, &'static Inventory>,
+	market_query: Query<'w, 's, &'static Market>,
+	godmode_query: Query<'w, 's, &'static GodMode>,
+	economy_query: Query<'w, 's, (&'static Economy, &'static Power, &'static Temperature, &'static Integrity, &'static Clock)>,
+	battery_query: Query<'w, 's, &'static Battery>,
+	debt_query: Query<'w, 's, &'static Debt>,
+	molecule_query: Query<'w, 's, (&'static Molecule, &'static Velocity, &'static Transform)>,
+}
+
+impl<'w, 's> SaveSources<'w, 's> {
+	pub fn capture(&self) -> SaveFile {
+		capture_save(
+			self.campaign_query.single(),
+			self.research_query.single(),
+			self.upgrades_query.single(),
+			self.walls_query.single(),
+			self.inventory_query.single(),
+			self.market_query.single(),
+			self.godmode_query.single(),
+			self.economy_query.get_single().ok().map(|(_, power, temperature, integrity, clock)| (power, temperature, integrity, clock)),
+			self.battery_query.get_single().ok(),
+			self.debt_query.get_single().ok(),
+			self.molecule_query.iter(),
+		)
+	}
+}
+
+// Everything a load writes progression into
+#[derive(SystemParam)]
+pub struct ProgressTargets<'w, 's> {
+	campaign_query: Query<'w, 's, &'static mut Campaign>,
+	research_query: Query<'w, 's, &'static mut Research>,
+	upgrades_query: Query<'w, 's, &'static mut Upgrades>,
+	walls_query: Query<'w, 's, &'static mut ReactorWalls>,
+	inventory_query: Query<'w, 's, &'static mut Inventory>,
+	market_query: Query<'w, 's, &'static mut Market>,
+	godmode_query: Query<'w, 's, &'static mut GodMode>,
+}
+
+impl<'w, 's> ProgressTargets<'w, 's> {
+	pub fn apply(&mut self, progress: &ProgressSave, god_mode: bool) {
+		apply_progress(
+			progress,
+			&mut self.campaign_query.single_mut(),
+			&mut self.research_query.single_mut(),
+			&mut self.upgrades_query.single_mut(),
+			&mut self.walls_query.single_mut(),
+			&mut self.inventory_query.single_mut(),
+			&mut self.market_query.single_mut(),
+		);
+		self.godmode_query.single_mut().0 = god_mode;
+	}
+}
+
+fn write_save (
+	profile: Res<ActiveProfile>,
+	sources: SaveSources,
+) {
+	write_save_file(&profile, SaveSlot::Manual, &sources.capture());
+}
+
+// The first time the start menu opens, before anything has been played or loaded
+fn capture_fresh_progress (
+	mut commands: Commands,
+	fresh_progress: Option<Res<FreshProgress>>,
+	sources: SaveSources,
+) {
+	if fresh_progress.is_none() {
+		commands.insert_resource(FreshProgress(sources.capture().progress));
+	}
+}
+
+fn autosave_periodically (
+	time: Res<Time>,
+	profile: Res<ActiveProfile>,
+	settings: Res<Settings>,
+	mut timer: ResMut<AutosaveTimer>,
+	crash_recovery: Res<CrashRecovery>,
+	pending_day: Option<Res<PendingDay>>,
+	sources: SaveSources,
+) {
+	// Don't overwrite the day being restored with the fresh one it replaces
+	if pending_day.is_some() || crash_recovery.available {
+		return;
+	}
+	// 0 turns the timer off, transitions still autosave
+	if settings.gameplay.autosave_interval <= 0.0 {
+		return;
+	}
+	if timer.0.duration().as_secs_f32() != settings.gameplay.autosave_interval {
+		timer.0 = Timer::from_seconds(settings.gameplay.autosave_interval, true);
+	}
+	if timer.0.tick(time.delta()).just_finished() {
+		write_save_file(&profile, SaveSlot::Autosave, &sources.capture());
+	}
+}
+
+// State is written to every frame by its driver, so change detection can't tell when it actually moved
+fn autosave_on_transition (
+	state: Res<State<GameState>>,
+	mut last_state: Local<Option<GameState>>,
+	profile: Res<ActiveProfile>,
+	crash_recovery: Res<CrashRecovery>,
+	pending_day: Option<Res<PendingDay>>,
+	sources: SaveSources,
+) {
+	let current = *state.current();
+	if *last_state == Some(current) {
+		return;
+	}
+	let first_frame = last_state.is_none();
+	*last_state = Some(current);
+	// Keep the crashed session's autosave until the player has decided whether to restore it
+	if first_frame || crash_recovery.available || pending_day.is_some() {
+		return;
+	}
+	write_save_file(&profile, SaveSlot::Autosave, &sources.capture());
+}
+
+// Leaves the lock in place, the next launch finding it means this one never exited cleanly
+fn check_session (
+	profile: Res<ActiveProfile>,
+	mut crash_recovery: ResMut<CrashRecovery>,
+) {
+	let lock_path = save_path(LOCK_FILE);
+	if lock_path.exists() {
+		crash_recovery.available = read_save(&profile, SaveSlot::Autosave).is_some();
+		warn!("Previous session did not exit cleanly");
+	}
+	if let Some(dir) = lock_path.parent() {
+		let _ = fs::create_dir_all(dir);
+	}
+	if let Err(error) = fs::write(&lock_path, "") {
+		warn!("Failed to write {}: {error}", lock_path.display());
+	}
+}
+
+fn end_session (
+	mut exit_events: EventReader<AppExit>,
+) {
+	if exit_events.iter().next().is_some() {
+		let _ = fs::remove_file(save_path(LOCK_FILE));
+	}
+}
+
+pub fn capture_save<'a> (
+	campaign: &Campaign,
+	research: &Research,
+	upgrades: &Upgrades,
+	walls: &ReactorWalls,
+	inventory: &Inventory,
+	market: &Market,
+	godmode: &GodMode,
+	economy: Option<(&Power, &Temperature, &Integrity, &Clock)>,
+	battery: Option<&Battery>,
+	debt: Option<&Debt>,
+	molecules: impl Iterator<Item = (&'a Molecule, &'a Velocity, &'a Transform)>,
+) -> SaveFile {
+	// A finished clock means the day is being wrapped up, not left part way
+	let day = match economy {
+		Some((power, temperature, integrity, clock)) if !clock.countdown.finished() => Some(DaySave {
+			current_power: power.current_power,
+			target_power: power.target_power,
+			generated: power.generated,
+			current_pressure: temperature.current_pressure,
+			max_pressure: temperature.max_pressure,
+			current_integrity: integrity.current_integrity,
+			max_integrity: integrity.max_integrity,
+			day_length: clock.countdown.duration().as_secs_f32(),
+			clock_remaining: (clock.countdown.duration() - clock.countdown.elapsed()).as_secs_f32(),
+			battery_stored: battery.map_or(0.0, |battery| battery.stored),
+			battery_discharging: battery.map_or(true, |battery| battery.discharging),
+			debt: debt.map_or(0.0, |debt| debt.balance),
+			molecules: molecules
+				.filter(|(molecule, _, _)| !molecule.reacted)
+				.map(|(molecule, velocity, transform)| MoleculeSave {
+					variant: molecule.variant,
+					position: (transform.translation.x, transform.translation.y),
+					velocity: (velocity.val.x, velocity.val.y),
+				})
+				.collect(),
+		}),
+		_ => None,
+	};
+
+	SaveFile {
+		version: SAVE_VERSION,
+		progress: ProgressSave {
+			campaign_day: campaign.day,
+			campaign_finished: campaign.finished,
+			results: campaign.results.clone(),
+			research_points: research.points,
+			research_completed: research.completed.to_vec(),
+			research_discovered: research.discovered.clone(),
+			credits: upgrades.credits,
+			upgrade_levels: upgrades.levels.to_vec(),
+			walls: walls.material,
+			inventory: inventory.clone(),
+			market: MOLECULE_TYPES.iter().map(|molecule_type| *market.get_entry(*molecule_type)).collect(),
+		},
+		god_mode: godmode.0,
+		day,
+	}
+}
+
+// Puts saved progression back onto the persistent entities
+pub fn apply_progress (
+	progress: &ProgressSave,
+	campaign: &mut Campaign,
+	research: &mut Research,
+	upgrades: &mut Upgrades,
+	walls: &mut ReactorWalls,
+	inventory: &mut Inventory,
+	market: &mut Market,
+) {
+	campaign.day = progress.campaign_day;
+	campaign.finished = progress.campaign_finished;
+	campaign.results = progress.results.clone();
+
+	// Nodes and upgrades added since the save keep their defaults
+	research.points = progress.research_points;
+	for (index, completed) in progress.research_completed.iter().enumerate().take(RESEARCH.len()) {
+		research.completed[index] = *completed;
+	}
+	research.discovered = progress.research_discovered.clone();
+	upgrades.credits = progress.credits;
+	for (index, level) in progress.upgrade_levels.iter().enumerate().take(UPGRADES.len()) {
+		upgrades.levels[index] = *level;
+	}
+
+	walls.material = progress.walls;
+	*inventory = progress.inventory.clone();
+	for (molecule_type, entry) in MOLECULE_TYPES.iter().zip(progress.market.iter()) {
+		*market.get_entry_mut(*molecule_type) = *entry;
+	}
+}
+
+fn apply_load (
+	mut commands: Commands,
+	mut load_request: ResMut<LoadRequest>,
+	mut state: ResMut<State<GameState>>,
+	profile: Res<ActiveProfile>,
+	mut targets: ProgressTargets,
+) {
+	if !load_request.pending {
+		return;
+	}
+	load_request.pending = false;
+	let resume = load_request.resume;
+
+	let save = match read_save(&profile, load_request.slot) {
+		Some(save) => save,
+		None => return,
+	};
+
+	targets.apply(&save.progress, save.god_mode);
+
+	if let (true, Some(day)) = (resume, save.day) {
+		commands.insert_resource(PendingDay(day));
+		state.push(GameState::Reactor).expect("Failed to change states");
+	}
+}
+
+// Overwrites the freshly started day with the saved one
+fn restore_day (
+	mut commands: Commands,
+	pending_day: Option<Res<PendingDay>>,
+	spritesheet: Res<SpriteSheets>,
+	mut economy_query: Query<(&Economy, &mut Power, &mut Temperature, &mut Integrity, &mut Clock)>,
+	mut battery_query: Query<&mut Battery>,
+	mut debt_query: Query<&mut Debt>,
+	mut grid_query: Query<&mut Grid>,
+	mut molecule_list_query: Query<&MoleculeList>,
+) {
+	let day = match pending_day {
+		Some(pending_day) => pending_day.0.clone(),
+		None => return,
+	};
+	// The day's entities only exist once the enter systems' commands have run
+	let (_economy, mut power, mut temperature, mut integrity, mut clock) = match economy_query.get_single_mut() {
+		Ok(economy) => economy,
+		Err(_) => return,
+	};
+	commands.remove_resource::<PendingDay>();
+
+	power.current_power = day.current_power;
+	power.target_power = day.target_power;
+	power.generated = day.generated;
+	temperature.current_pressure = day.current_pressure;
+	temperature.max_pressure = day.max_pressure;
+	integrity.current_integrity = day.current_integrity;
+	integrity.max_integrity = day.max_integrity;
+	clock.countdown = Timer::from_seconds(day.day_length, false);
+	clock.countdown.set_elapsed(Duration::from_secs_f32((day.day_length - day.clock_remaining).max(0.0)));
+
+	let mut battery = battery_query.single_mut();
+	battery.stored = day.battery_stored;
+	battery.discharging = day.battery_discharging;
+
+	// Hours already played were settled before the save
+	let hour = clock.hour();
+	let mut debt = debt_query.single_mut();
+	debt.balance = day.debt;
+	debt.hour = hour;
+	debt.seen_generated = day.generated;
+	grid_query.single_mut().hour = hour;
+
+	for molecule in day.molecules.iter() {
+		spawn_molecule(
+			molecule.variant,
+			&mut commands,
+			&spritesheet,
+			&mut molecule_list_query,
+			0,
+			Vec3::new(molecule.position.0, molecule.position.1, 900.0),
+			Vec2::new(molecule.velocity.0, molecule.velocity.1),
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::economy::Trend;
+
+	// Persistent entities as they'd be partway through a campaign
+	struct Progress {
+		campaign: Campaign,
+		research: Research,
+		upgrades: Upgrades,
+		walls: ReactorWalls,
+		inventory: Inventory,
+		market: Market,
+	}
+
+	fn market_entry (
+		seed: f32,
+	) -> MarketEntry {
+		MarketEntry {
+			demand: 1.0 + seed,
+			drift: -seed / 10.0,
+			last_price: 3.0 * seed,
+			trend: Trend::Rising,
+		}
+	}
+
+	fn fresh_progress () -> Progress {
+		Progress {
+			campaign: Campaign {
+				day: 0,
+				results: Vec::new(),
+				finished: false,
+			},
+			research: Research {
+				points: 0.0,
+				completed: [false; RESEARCH.len()],
+				discovered: Vec::new(),
+			},
+			upgrades: Upgrades {
+				credits: 0.0,
+				levels: [0; UPGRADES.len()],
+			},
+			walls: ReactorWalls {
+				material: WallMaterial::Steel,
+			},
+			inventory: Inventory {
+				red_count: 0,
+				blue_count: 0,
+				orange_count: 0,
+				purple_count: 0,
+				gray_count: 0,
+			},
+			market: Market {
+				red_entry: market_entry(0.0),
+				blue_entry: market_entry(0.0),
+				orange_entry: market_entry(0.0),
+				purple_entry: market_entry(0.0),
+				gray_entry: market_entry(0.0),
+				tick: Timer::from_seconds(1.0, true),
+			},
+		}
+	}
+
+	fn played_progress () -> Progress {
+		let mut progress = fresh_progress();
+		progress.campaign.day = 3;
+		progress.campaign.results = vec![
+			DayResult {day: 0, power: 2100.0, target: 2000.0},
+			DayResult {day: 1, power: 3300.0, target: 3000.0},
+		];
+		progress.research.points = 12.5;
+		progress.research.completed[0] = true;
+		progress.research.completed[4] = true;
+		progress.research.discovered = vec![MoleculeType::Purple];
+		progress.upgrades.credits = 420.0;
+		progress.upgrades.levels[1] = 2;
+		progress.upgrades.levels[6] = 1;
+		progress.walls.material = WallMaterial::Ceramic;
+		progress.inventory.blue_count = 7;
+		progress.inventory.gray_count = 2;
+		progress.market.orange_entry = market_entry(0.5);
+		progress.market.gray_entry = market_entry(0.25);
+		progress
+	}
+
+	fn capture (
+		progress: &Progress,
+		economy: Option<(&Power, &Temperature, &Integrity, &Clock)>,
+		molecules: &[(Molecule, Velocity, Transform)],
+	) -> SaveFile {
+		capture_save(
+			&progress.campaign,
+			&progress.research,
+			&progress.upgrades,
+			&progress.walls,
+			&progress.inventory,
+			&progress.market,
+			&GodMode(true),
+			economy,
+			None,
+			None,
+			molecules.iter().map(|(molecule, velocity, transform)| (molecule, velocity, transform)),
+		)
+	}
+
+	fn to_ron (
+		save: &SaveFile,
+	) -> String {
+		ron::to_string(save).expect("Save should serialize")
+	}
+
+	#[test]
+	fn progress_round_trips () {
+		let played = played_progress();
+		let save = capture(&played, None, &[]);
+		let text = to_ron(&save);
+		let loaded: SaveFile = ron::from_str(&text).expect("Save should deserialize");
+
+		let mut restored = fresh_progress();
+		apply_progress(
+			&loaded.progress,
+			&mut restored.campaign,
+			&mut restored.research,
+			&mut restored.upgrades,
+			&mut restored.walls,
+			&mut restored.inventory,
+			&mut restored.market,
+		);
+
+		assert_eq!(restored.campaign.day, played.campaign.day);
+		assert_eq!(restored.campaign.finished, played.campaign.finished);
+		assert_eq!(restored.campaign.results.len(), played.campaign.results.len());
+		assert_eq!(restored.research.points, played.research.points);
+		assert_eq!(restored.research.completed, played.research.completed);
+		assert_eq!(restored.research.discovered, played.research.discovered);
+		assert_eq!(restored.upgrades.credits, played.upgrades.credits);
+		assert_eq!(restored.upgrades.levels, played.upgrades.levels);
+		assert_eq!(restored.walls.material, played.walls.material);
+		for molecule_type in MOLECULE_TYPES {
+			assert_eq!(restored.inventory.get_count(molecule_type), played.inventory.get_count(molecule_type));
+			let (restored_entry, played_entry) = (restored.market.get_entry(molecule_type), played.market.get_entry(molecule_type));
+			assert_eq!(restored_entry.demand, played_entry.demand);
+			assert_eq!(restored_entry.drift, played_entry.drift);
+			assert_eq!(restored_entry.last_price, played_entry.last_price);
+			assert_eq!(restored_entry.trend, played_entry.trend);
+		}
+		assert!(loaded.god_mode);
+		assert!(loaded.day.is_none());
+
+		// Capturing the restored state gives back the same file
+		assert_eq!(to_ron(&capture(&restored, None, &[])), text);
+	}
+
+	#[test]
+	fn day_round_trips () {
+		let progress = played_progress();
+		let power = Power {current_power: 850.0, target_power: 4500.0, generated: 1200.0};
+		let temperature = Temperature {current_pressure: 31.0, max_pressure: 55.0, thermometer_sprite: 0};
+		let integrity = Integrity {current_integrity: 80.0, max_integrity: 100.0};
+		let mut clock = Clock {countdown: Timer::from_seconds(180.0, false), time_sprite: 0};
+		clock.countdown.set_elapsed(Duration::from_secs_f32(60.0));
+		let molecules = [
+			(Molecule {variant: MoleculeType::Red, mass: 0.8, reacted: false}, Velocity {val: Vec2::new(1.0, -2.0)}, Transform::from_xyz(10.0, 20.0, 900.0)),
+			// Already used up by a reaction this frame
+			(Molecule {variant: MoleculeType::Blue, mass: 1.6, reacted: true}, Velocity {val: Vec2::ZERO}, Transform::default()),
+		];
+
+		let save = capture(&progress, Some((&power, &temperature, &integrity, &clock)), &molecules);
+		let text = to_ron(&save);
+		let loaded: SaveFile = ron::from_str(&text).expect("Save should deserialize");
+		let day = loaded.day.expect("A day in progress should be saved");
+
+		assert_eq!(day.current_power, power.current_power);
+		assert_eq!(day.target_power, power.target_power);
+		assert_eq!(day.generated, power.generated);
+		assert_eq!(day.current_pressure, temperature.current_pressure);
+		assert_eq!(day.max_pressure, temperature.max_pressure);
+		assert_eq!(day.current_integrity, integrity.current_integrity);
+		assert_eq!(day.max_integrity, integrity.max_integrity);
+		assert_eq!(day.day_length, 180.0);
+		assert!((day.clock_remaining - 120.0).abs() < 0.001);
+		assert_eq!(day.molecules, vec![MoleculeSave {
+			variant: MoleculeType::Red,
+			position: (10.0, 20.0),
+			velocity: (1.0, -2.0),
+		}]);
+	}
+
+	// A saved day put back into a reactor its enter systems just built
+	#[test]
+	fn day_restores_into_reactor () {
+		let mut world = World::new();
+		world.insert_resource(SpriteSheets {
+			red_molecule: Handle::default(),
+			blue_molecule: Handle::default(),
+			orange_molecule: Handle::default(),
+			purple_molecule: Handle::default(),
+			gray_molecule: Handle::default(),
+			lab: Handle::default(),
+			thermometer: Handle::default(),
+			logbook_button: Handle::default(),
+		});
+		world.insert_resource(PendingDay(DaySave {
+			current_power: 850.0,
+			target_power: 4500.0,
+			generated: 1200.0,
+			current_pressure: 31.0,
+			max_pressure: 55.0,
+			current_integrity: 80.0,
+			max_integrity: 100.0,
+			day_length: 160.0,
+			clock_remaining: 50.0,
+			battery_stored: 240.0,
+			battery_discharging: false,
+			debt: 300.0,
+			molecules: vec![
+				MoleculeSave {variant: MoleculeType::Orange, position: (10.0, 20.0), velocity: (1.0, -2.0)},
+				MoleculeSave {variant: MoleculeType::Gray, position: (-30.0, 5.0), velocity: (0.0, 4.0)},
+			],
+		}));
+
+		let economy = world.spawn()
+			.insert(Economy)
+			.insert(Power {current_power: 0.0, target_power: 1000.0, generated: 0.0})
+			.insert(Temperature {current_pressure: 0.0, max_pressure: 50.0, thermometer_sprite: 0})
+			.insert(Integrity {current_integrity: 100.0, max_integrity: 100.0})
+			.insert(Clock {countdown: Timer::from_seconds(180.0, false), time_sprite: 0})
+			.id();
+		let battery = world.spawn()
+			.insert(Battery {stored: 0.0, capacity: 600.0, charge_rate: 60.0, discharge_rate: 25.0, self_discharge: 0.01, incoming: 0.0, discharging: true})
+			.id();
+		let debt = world.spawn()
+			.insert(Debt {balance: 0.0, hour: 0, seen_generated: 0.0})
+			.id();
+		let grid = world.spawn()
+			.insert(Grid {hour: 0, results: Vec::new()})
+			.id();
+		let molecule_list = world.spawn().id();
+		world.entity_mut(molecule_list).insert(MoleculeList(molecule_list));
+
+		SystemStage::single(restore_day).run(&mut world);
+
+		assert!(!world.contains_resource::<PendingDay>());
+		let power = world.get::<Power>(economy).unwrap();
+		assert_eq!(power.current_power, 850.0);
+		assert_eq!(power.target_power, 4500.0);
+		assert_eq!(power.generated, 1200.0);
+		let temperature = world.get::<Temperature>(economy).unwrap();
+		assert_eq!(temperature.current_pressure, 31.0);
+		assert_eq!(temperature.max_pressure, 55.0);
+		let integrity = world.get::<Integrity>(economy).unwrap();
+		assert_eq!(integrity.current_integrity, 80.0);
+		assert_eq!(integrity.max_integrity, 100.0);
+
+		// 110 of 160 seconds gone is partway through the sixth hour
+		let clock = world.get::<Clock>(economy).unwrap();
+		assert_eq!(clock.countdown.duration().as_secs_f32(), 160.0);
+		assert!((clock.countdown.elapsed().as_secs_f32() - 110.0).abs() < 0.001);
+		assert_eq!(clock.hour(), 5);
+
+		// Hours before the save were already settled, so neither the grid nor interest goes over them again
+		assert_eq!(world.get::<Grid>(grid).unwrap().hour, 5);
+		let debt = world.get::<Debt>(debt).unwrap();
+		assert_eq!(debt.balance, 300.0);
+		assert_eq!(debt.hour, 5);
+		assert_eq!(debt.seen_generated, 1200.0);
+
+		let battery = world.get::<Battery>(battery).unwrap();
+		assert_eq!(battery.stored, 240.0);
+		assert!(!battery.discharging);
+
+		let mut molecules: Vec<MoleculeSave> = world.query::<(&Molecule, &Velocity, &Transform)>()
+			.iter(&world)
+			.map(|(molecule, velocity, transform)| MoleculeSave {
+				variant: molecule.variant,
+				position: (transform.translation.x, transform.translation.y),
+				velocity: (velocity.val.x, velocity.val.y),
+			})
+			.collect();
+		molecules.sort_by_key(|molecule| molecule.variant as usize);
+		assert_eq!(molecules, vec![
+			MoleculeSave {variant: MoleculeType::Orange, position: (10.0, 20.0), velocity: (1.0, -2.0)},
+			MoleculeSave {variant: MoleculeType::Gray, position: (-30.0, 5.0), velocity: (0.0, 4.0)},
+		]);
+		assert_eq!(world.get::<Children>(molecule_list).unwrap().len(), 2);
+	}
+
+	#[test]
+	fn migrate_rejects_other_versions () {
+		let mut save = capture(&fresh_progress(), None, &[]);
+		assert!(migrate(save.clone()).is_some());
+
+		save.version = SAVE_VERSION + 1;
+		assert!(migrate(save.clone()).is_none());
+		save.version = 0;
+		assert!(migrate(save).is_none());
+	}
+}
@@ -0,0 +1,498 @@
+use std::{fs, path::PathBuf};
+
+use bevy::{prelude::*, window::{PresentMode, WindowMode}, ui::FocusPolicy};
+use serde::{Serialize, Deserialize};
+
+use crate::{
+	GameState, INVISIBLE, ASPECT_RATIO,
+	audio::Volume,
+	profiles::ActiveProfile,
+	save::write_ron,
+	controls::ControlSettings,
+	locale::{Locale, DEFAULT_LANGUAGE},
+	accessibility::AccessibilitySettings,
+	start_menu::{UiAssets, UiButton, OPTION_COLOR, SELECTED_OPTION_COLOR, despawn_screen, show_buttons},
+};
+
+// ---------- PLUGINS ----------
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.insert_resource(DisplayMenu {
+			dirty: false,
+		})
+		.add_system(sync_volume.before(write_settings))
+		.add_system(write_settings)
+		.add_system(apply_display)
+		// DISPLAY MENU
+		.add_system_set(SystemSet::on_enter(GameState::SettingsDisplayMenu)
+			.with_system(open_display_menu)
+		)
+		.add_system_set(SystemSet::on_update(GameState::SettingsDisplayMenu)
+			.with_system(show_buttons)
+			.with_system(button_display_option)
+			.with_system(button_display_back)
+			.with_system(load_display_menu.after(button_display_option))
+		)
+		.add_system_set(SystemSet::on_exit(GameState::SettingsDisplayMenu)
+			.with_system(despawn_screen::<DisplayMenuUi>)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+const SETTINGS_FILE: &str = "settings.ron";
+
+// Contents of the settings file, Settings is this with the active profile's overrides on top
+pub struct SharedSettings(pub Settings);
+
+// All 16:9 so the layout never stretches
+const RESOLUTIONS: [(f32, f32); 4] = [
+	(1280.0, 720.0),
+	(1600.0, 900.0),
+	(1920.0, 1080.0),
+	(2560.0, 1440.0),
+];
+
+const DISPLAY_MODES: [DisplayMode; 3] = [
+	DisplayMode::Windowed,
+	DisplayMode::Borderless,
+	DisplayMode::Fullscreen,
+];
+
+// Missing keys take their default and unknown ones are skipped, so older and newer files both load
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+	pub audio: AudioSettings,
+	pub display: DisplaySettings,
+	pub gameplay: GameplaySettings,
+	pub controls: ControlSettings,
+	// Code of a string file in assets/locale
+	pub language: String,
+	pub accessibility: AccessibilitySettings,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct AudioSettings {
+	pub bgm: f32,
+	pub sfx: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum DisplayMode {
+	Windowed,
+	Borderless,
+	Fullscreen,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct DisplaySettings {
+	pub width: f32,
+	pub height: f32,
+	pub mode: DisplayMode,
+	pub vsync: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct GameplaySettings {
+	// Whether the battery feeds the grid from the start of the day
+	pub battery_discharging: bool,
+	// Seconds between autosaves during a day, 0 turns them off
+	pub autosave_interval: f32,
+}
+
+// Set while the display menu needs rebuilding
+struct DisplayMenu {
+	dirty: bool,
+}
+
+impl Default for Settings {
+	fn default() -> Self {
+		Self {
+			audio: AudioSettings::default(),
+			display: DisplaySettings::default(),
+			gameplay: GameplaySettings::default(),
+			controls: ControlSettings::default(),
+			language: DEFAULT_LANGUAGE.to_string(),
+			accessibility: AccessibilitySettings::default(),
+		}
+	}
+}
+
+impl Default for AudioSettings {
+	fn default() -> Self {
+		Self {
+			bgm: 0.1,
+			sfx: 0.1,
+		}
+	}
+}
+
+impl Default for DisplaySettings {
+	fn default() -> Self {
+		Self {
+			width: 900.0 * ASPECT_RATIO,
+			height: 900.0,
+			mode: DisplayMode::Windowed,
+			vsync: true,
+		}
+	}
+}
+
+impl Default for GameplaySettings {
+	fn default() -> Self {
+		Self {
+			battery_discharging: true,
+			autosave_interval: 30.0,
+		}
+	}
+}
+
+impl DisplayMode {
+	fn name<'a>(&self, locale: &'a Locale) -> &'a str {
+		locale.get(match self {
+			DisplayMode::Windowed => "display.windowed",
+			DisplayMode::Borderless => "display.borderless",
+			DisplayMode::Fullscreen => "display.fullscreen",
+		})
+	}
+
+	pub fn window_mode(&self) -> WindowMode {
+		match self {
+			DisplayMode::Windowed => WindowMode::Windowed,
+			DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+			DisplayMode::Fullscreen => WindowMode::Fullscreen,
+		}
+	}
+}
+
+impl DisplaySettings {
+	pub fn present_mode(&self) -> PresentMode {
+		if self.vsync {PresentMode::Fifo} else {PresentMode::Immediate}
+	}
+}
+
+// ---------- COMPONENTS ----------
+#[derive(Component)]
+struct DisplayMenuUi;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+enum DisplayOption {
+	Resolution(f32, f32),
+	Mode(DisplayMode),
+	Vsync(bool),
+}
+
+#[derive(Component)]
+struct DisplayBackButton;
+
+impl DisplayOption {
+	fn label(&self, locale: &Locale) -> String {
+		match self {
+			DisplayOption::Resolution(width, height) => format!("{width:.0}x{height:.0}"),
+			DisplayOption::Mode(mode) => mode.name(locale).to_string(),
+			DisplayOption::Vsync(true) => locale.get("display.on").to_string(),
+			DisplayOption::Vsync(false) => locale.get("display.off").to_string(),
+		}
+	}
+
+	fn is_selected(&self, display: &DisplaySettings) -> bool {
+		match *self {
+			// Within a pixel, the default width comes from the aspect ratio
+			DisplayOption::Resolution(width, height) => (display.width - width).abs() < 1.0 && (display.height - height).abs() < 1.0,
+			DisplayOption::Mode(mode) => display.mode == mode,
+			DisplayOption::Vsync(vsync) => display.vsync == vsync,
+		}
+	}
+
+	fn apply(&self, display: &mut DisplaySettings) {
+		match *self {
+			DisplayOption::Resolution(width, height) => {
+				display.width = width;
+				display.height = height;
+			}
+			DisplayOption::Mode(mode) => display.mode = mode,
+			DisplayOption::Vsync(vsync) => display.vsync = vsync,
+		}
+	}
+}
+
+impl Settings {
+	pub fn volume(&self) -> Volume {
+		Volume {
+			bgm: self.audio.bgm,
+			sfx: self.audio.sfx,
+		}
+	}
+}
+
+// ---------- SYSTEMS ----------
+pub fn settings_path () -> PathBuf {
+	dirs::config_dir()
+		.map(|dir| dir.join("mole_rancher"))
+		.unwrap_or_default()
+		.join(SETTINGS_FILE)
+}
+
+// Read once in main, before the window is created
+pub fn read_settings () -> Settings {
+	let text = match fs::read_to_string(settings_path()) {
+		Ok(text) => text,
+		Err(_) => return Settings::default(),
+	};
+	ron::from_str(&text).unwrap_or_else(|error| {
+		eprintln!("Ignoring unreadable settings, using defaults: {error}");
+		Settings::default()
+	})
+}
+
+// The audio menu edits Volume directly
+fn sync_volume (
+	volume: Res<Volume>,
+	mut settings: ResMut<Settings>,
+) {
+	if volume.is_changed() && !volume.is_added() && settings.volume() != *volume {
+		settings.audio.bgm = volume.bgm;
+		settings.audio.sfx = volume.sfx;
+	}
+}
+
+// Display and language stay with the machine in the shared file,
+// any other section that was edited becomes an override of the active profile only
+fn write_settings (
+	settings: Res<Settings>,
+	mut shared: ResMut<SharedSettings>,
+	mut profile: ResMut<ActiveProfile>,
+) {
+	if !settings.is_changed() || settings.is_added() {
+		return;
+	}
+
+	if settings.display != shared.0.display || settings.language != shared.0.language {
+		shared.0.display = settings.display.clone();
+		shared.0.language = settings.language.clone();
+		write_ron(&settings_path(), &shared.0);
+	}
+
+	// Sections that no longer match what the profile would be given were just edited
+	let mut expected = shared.0.clone();
+	profile.apply_overrides(&mut expected);
+	if settings.audio == expected.audio
+	&& settings.gameplay == expected.gameplay
+	&& settings.controls == expected.controls
+	&& settings.accessibility == expected.accessibility {
+		return;
+	}
+	if settings.audio != expected.audio {
+		profile.data.overrides.audio = Some(settings.audio.clone());
+	}
+	if settings.gameplay != expected.gameplay {
+		profile.data.overrides.gameplay = Some(settings.gameplay.clone());
+	}
+	if settings.controls != expected.controls {
+		profile.data.overrides.controls = Some(settings.controls.clone());
+	}
+	if settings.accessibility != expected.accessibility {
+		profile.data.overrides.accessibility = Some(settings.accessibility.clone());
+	}
+	profile.write();
+}
+
+// Pushes display changes to the window, whichever menu made them
+fn apply_display (
+	settings: Res<Settings>,
+	mut windows: ResMut<Windows>,
+) {
+	if !settings.is_changed() || settings.is_added() {
+		return;
+	}
+	let display = &settings.display;
+	if let Some(window) = windows.get_primary_mut() {
+		if window.requested_width() != display.width || window.requested_height() != display.height {
+			window.set_resolution(display.width, display.height);
+		}
+		if window.mode() != display.mode.window_mode() {
+			window.set_mode(display.mode.window_mode());
+		}
+		if window.present_mode() != display.present_mode() {
+			window.set_present_mode(display.present_mode());
+		}
+	}
+}
+
+fn open_display_menu (
+	mut display_menu: ResMut<DisplayMenu>,
+) {
+	display_menu.dirty = true;
+}
+
+// Rebuilt whenever an option is picked so the selection moves
+fn load_display_menu (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	settings: Res<Settings>,
+	locale: Res<Locale>,
+	mut display_menu: ResMut<DisplayMenu>,
+	ui_query: Query<Entity, With<DisplayMenuUi>>,
+) {
+	if !display_menu.dirty {
+		return;
+	}
+	display_menu.dirty = false;
+	for entity in ui_query.iter() {
+		commands.entity(entity).despawn_recursive();
+	}
+
+	let text_style = TextStyle {
+		font: ui_assets.font.clone(),
+		font_size: 28.0,
+		color: ui_assets.text_color,
+	};
+
+	let rows = [
+		("display.resolution", RESOLUTIONS.iter().map(|(width, height)| DisplayOption::Resolution(*width, *height)).collect::<Vec<_>>()),
+		("display.mode", DISPLAY_MODES.iter().map(|mode| DisplayOption::Mode(*mode)).collect()),
+		("display.vsync", vec![DisplayOption::Vsync(true), DisplayOption::Vsync(false)]),
+	];
+
+	commands
+		// Node Bundle to hold the Display Menu UI elements
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				margin: UiRect::all(Val::Auto),
+				flex_direction: FlexDirection::ColumnReverse,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(DisplayMenuUi)
+		.insert(Name::new("Display Menu UI"))
+		.with_children(|parent| {
+
+			// Option Rows
+			for (title, options) in rows {
+				parent
+					.spawn_bundle(NodeBundle {
+						style: Style {
+							size: Size::new(Val::Px(900.0), Val::Px(54.0)),
+							justify_content: JustifyContent::SpaceBetween,
+							align_items: AlignItems::Center,
+							..default()
+						},
+						color: INVISIBLE.into(),
+						..default()
+					})
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							locale.get(title),
+							text_style.clone(),
+						));
+
+						parent
+							.spawn_bundle(NodeBundle {
+								color: INVISIBLE.into(),
+								..default()
+							})
+							.with_children(|parent| {
+								for option in options {
+									// Option Button, highlighted while it's the current choice
+									parent
+										.spawn_bundle(ButtonBundle {
+											style: Style {
+												size: Size::new(Val::Px(160.0), Val::Px(44.0)),
+												margin: UiRect::all(Val::Px(5.0)),
+												justify_content: JustifyContent::Center,
+												align_items: AlignItems::Center,
+												..default()
+											},
+											color: if option.is_selected(&settings.display) {SELECTED_OPTION_COLOR.into()} else {OPTION_COLOR.into()},
+											..default()
+										})
+										.insert(option)
+										.with_children(|parent| {
+											parent.spawn_bundle(TextBundle::from_section(
+												option.label(&locale),
+												text_style.clone(),
+											));
+										});
+								}
+							});
+					});
+			}
+
+			// Back Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: Style {
+						size: Size::new(Val::Px(250.0), Val::Px(65.0)),
+						margin: UiRect::all(Val::Px(20.0)),
+						justify_content: JustifyContent::Center,
+						align_items: AlignItems::Center,
+						..default()
+					},
+					color: ui_assets.button_color.into(),
+					..default()
+				})
+				.insert(DisplayBackButton)
+				.insert(UiButton)
+				.with_children(|parent| {
+
+					// Back Button Sprite
+					parent
+						.spawn_bundle(ImageBundle {
+							style: Style {
+								size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+								justify_content: JustifyContent::Center,
+								align_items: AlignItems::Center,
+								..Default::default()
+							},
+						image: ui_assets.button_idle.clone().into(),
+						..Default::default()
+					})
+					.insert(FocusPolicy::Pass)
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							locale.get("menu.back"),
+							TextStyle {
+								font: ui_assets.font.clone(),
+								font_size: 40.0,
+								color: ui_assets.text_color,
+							},
+						));
+					});
+				});
+		});
+}
+
+fn button_display_option (
+	interaction_query: Query<(&DisplayOption, &Interaction), Changed<Interaction>>,
+	mut settings: ResMut<Settings>,
+	mut display_menu: ResMut<DisplayMenu>,
+) {
+	for (option, interaction) in interaction_query.iter() {
+		if *interaction == Interaction::Clicked && !option.is_selected(&settings.display) {
+			option.apply(&mut settings.display);
+			display_menu.dirty = true;
+		}
+	}
+}
+
+fn button_display_back (
+	interaction_query: Query<(&DisplayBackButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, interaction) in interaction_query.iter() {
+		if *interaction == Interaction::Clicked {
+			state.pop().expect("Failed to change states");
+		}
+	}
+}
@@ -0,0 +1,411 @@
+use std::fmt::Display;
+
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+	GameState, INVISIBLE,
+	start_menu::{despawn_screen, UiAssets},
+	reactor::ReactorWalls,
+	enums::WallMaterial,
+	research::{Research, ResearchEffect},
+	locale::Locale,
+};
+
+// ---------- PLUGINS ----------
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+	fn build(&self, app: &mut App) {
+		app
+		.add_startup_system(generate_upgrades)
+		.add_system_set(SystemSet::on_enter(GameState::Shop)
+			.with_system(load_shop)
+		)
+		.add_system_set(SystemSet::on_update(GameState::Shop)
+			.with_system(button_upgrade)
+			.with_system(button_exit_shop)
+			.with_system(update_shop_text)
+		)
+		.add_system_set(SystemSet::on_exit(GameState::Shop)
+			.with_system(despawn_screen::<ShopUi>)
+		)
+		;
+	}
+}
+
+// ---------- RESOURCES ----------
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum UpgradeKind {
+	// Grows the reactor's Dimensions
+	ReactorSize,
+	// Raises the walls' max pressure
+	PressureTolerance,
+	// Lowers molecule costs
+	Discount,
+	// Walls pull bouncing molecules back towards ambient speed faster
+	Cooling,
+	// Holds more charge
+	BatteryCapacity,
+	// Swaps the installed wall material
+	Walls(WallMaterial),
+}
+
+pub struct UpgradeDef {
+	pub kind: UpgradeKind,
+	// Locale key
+	pub name: &'static str,
+	// Price of the first level, each level after costs this much more again
+	pub cost: f32,
+	pub max_level: usize,
+}
+
+pub const UPGRADES: [UpgradeDef; 9] = [
+	UpgradeDef {
+		kind: UpgradeKind::ReactorSize,
+		name: "upgrade.bigger_vessel",
+		cost: 400.0,
+		max_level: 3,
+	},
+	UpgradeDef {
+		kind: UpgradeKind::PressureTolerance,
+		name: "upgrade.reinforced_seals",
+		cost: 300.0,
+		max_level: 5,
+	},
+	UpgradeDef {
+		kind: UpgradeKind::Discount,
+		name: "upgrade.bulk_supplier",
+		cost: 500.0,
+		max_level: 4,
+	},
+	UpgradeDef {
+		kind: UpgradeKind::Cooling,
+		name: "upgrade.coolant_jacket",
+		cost: 250.0,
+		max_level: 4,
+	},
+	UpgradeDef {
+		kind: UpgradeKind::BatteryCapacity,
+		name: "upgrade.extra_cells",
+		cost: 350.0,
+		max_level: 5,
+	},
+	// Starting walls, always owned so they can be put back
+	UpgradeDef {
+		kind: UpgradeKind::Walls(WallMaterial::Steel),
+		name: "upgrade.steel_walls",
+		cost: 0.0,
+		max_level: 0,
+	},
+	UpgradeDef {
+		kind: UpgradeKind::Walls(WallMaterial::Ceramic),
+		name: "upgrade.ceramic_walls",
+		cost: 600.0,
+		max_level: 1,
+	},
+	UpgradeDef {
+		kind: UpgradeKind::Walls(WallMaterial::Tungsten),
+		name: "upgrade.tungsten_walls",
+		cost: 1200.0,
+		max_level: 1,
+	},
+	UpgradeDef {
+		kind: UpgradeKind::Walls(WallMaterial::Graphene),
+		name: "upgrade.graphene_walls",
+		cost: 2500.0,
+		max_level: 1,
+	},
+];
+
+// Effect of a single level of each upgrade
+const SIZE_PER_LEVEL: f32 = 0.04;
+const PRESSURE_PER_LEVEL: f32 = 0.1;
+const DISCOUNT_PER_LEVEL: f32 = 0.05;
+const COOLING_PER_LEVEL: f32 = 0.05;
+const BATTERY_PER_LEVEL: f32 = 0.5;
+
+// ---------- COMPONENTS ----------
+// Bought upgrades and spare credits, kept between days
+#[derive(Component)]
+pub struct Upgrades {
+	pub credits: f32,
+	// Level of each entry in UPGRADES
+	pub levels: [usize; UPGRADES.len()],
+}
+
+impl Upgrades {
+	pub fn level(&self, kind: UpgradeKind) -> usize {
+		UPGRADES.iter()
+			.position(|upgrade| upgrade.kind == kind)
+			.map_or(0, |index| self.levels[index])
+	}
+
+	pub fn next_cost(&self, index: usize) -> f32 {
+		UPGRADES[index].cost * (self.levels[index] + 1) as f32
+	}
+
+	// Multiplier on the reactor's width and height
+	pub fn size_scale(&self) -> f32 {
+		1.0 + SIZE_PER_LEVEL * self.level(UpgradeKind::ReactorSize) as f32
+	}
+
+	// Multiplier on max pressure
+	pub fn pressure_scale(&self) -> f32 {
+		1.0 + PRESSURE_PER_LEVEL * self.level(UpgradeKind::PressureTolerance) as f32
+	}
+
+	// Multiplier on molecule costs
+	pub fn cost_scale(&self) -> f32 {
+		1.0 - DISCOUNT_PER_LEVEL * self.level(UpgradeKind::Discount) as f32
+	}
+
+	// Added to the wall material's conductivity
+	pub fn cooling(&self) -> f32 {
+		COOLING_PER_LEVEL * self.level(UpgradeKind::Cooling) as f32
+	}
+
+	// Multiplier on battery capacity
+	pub fn battery_scale(&self) -> f32 {
+		1.0 + BATTERY_PER_LEVEL * self.level(UpgradeKind::BatteryCapacity) as f32
+	}
+}
+
+#[derive(Component)]
+struct ShopUi;
+
+#[derive(Component)]
+struct UpgradeButton(usize);
+
+#[derive(Component)]
+struct UpgradeText(usize);
+
+#[derive(Component)]
+struct CreditsText;
+
+#[derive(Component)]
+struct ShopExitButton;
+
+// ---------- SYSTEMS ----------
+fn generate_upgrades (
+	mut commands: Commands,
+) {
+	commands
+		.spawn()
+		.insert(Upgrades {
+			credits: 0.0,
+			levels: [0; UPGRADES.len()],
+		})
+		.insert(Name::new("Upgrades"));
+}
+
+fn load_shop (
+	mut commands: Commands,
+	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
+) {
+	let button_style = Style {
+		size: Size::new(Val::Px(700.0), Val::Px(55.0)),
+		margin: UiRect::all(Val::Px(8.0)),
+		justify_content: JustifyContent::Center,
+		align_items: AlignItems::Center,
+		..default()
+	};
+
+	let text_style = TextStyle {
+		font: ui_assets.font.clone(),
+		font_size: 32.0,
+		color: ui_assets.text_color,
+	};
+
+	commands
+		.spawn_bundle(NodeBundle {
+			style: Style {
+				margin: UiRect::all(Val::Auto),
+				flex_direction: FlexDirection::ColumnReverse,
+				align_items: AlignItems::Center,
+				..default()
+			},
+			color: INVISIBLE.into(),
+			..default()
+		})
+		.insert(ShopUi)
+		.insert(Name::new("Shop UI"))
+		.with_children(|parent| {
+			parent
+				.spawn_bundle(TextBundle::from_section(
+					"",
+					text_style.clone(),
+				))
+				.insert(CreditsText);
+
+			for index in 0..UPGRADES.len() {
+
+				// Upgrade Button
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: button_style.clone(),
+						color: ui_assets.button_color.into(),
+						..default()
+					})
+					.insert(UpgradeButton(index))
+					.with_children(|parent| {
+
+						// Upgrade Button Sprite
+						parent
+							.spawn_bundle(ImageBundle {
+								style: Style {
+									size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+									justify_content: JustifyContent::Center,
+									align_items: AlignItems::Center,
+									..Default::default()
+								},
+							image: ui_assets.button_idle.clone().into(),
+							..Default::default()
+						})
+						.insert(FocusPolicy::Pass)
+						.with_children(|parent| {
+							parent
+								.spawn_bundle(TextBundle::from_section(
+									"",
+									text_style.clone(),
+								))
+								.insert(UpgradeText(index));
+						});
+					});
+			}
+
+			// Back Button
+			parent
+				.spawn_bundle(ButtonBundle {
+					style: Style {
+						size: Size::new(Val::Px(250.0), Val::Px(55.0)),
+						..button_style.clone()
+					},
+					color: ui_assets.button_color.into(),
+					..default()
+				})
+				.insert(ShopExitButton)
+				.with_children(|parent| {
+
+					// Back Button Sprite
+					parent
+						.spawn_bundle(ImageBundle {
+							style: Style {
+								size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+								justify_content: JustifyContent::Center,
+								align_items: AlignItems::Center,
+								..Default::default()
+							},
+						image: ui_assets.button_idle.clone().into(),
+						..Default::default()
+					})
+					.insert(FocusPolicy::Pass)
+					.with_children(|parent| {
+						parent.spawn_bundle(TextBundle::from_section(
+							locale.get("menu.back"),
+							text_style.clone(),
+						));
+					});
+				});
+		});
+}
+
+fn update_shop_text (
+	upgrades_query: Query<&Upgrades>,
+	walls_query: Query<&ReactorWalls>,
+	research_query: Query<&Research>,
+	mut credits_text_query: Query<&mut Text, (With<CreditsText>, Without<UpgradeText>)>,
+	mut upgrade_text_query: Query<(&UpgradeText, &mut Text)>,
+	locale: Res<Locale>,
+) {
+	let upgrades = upgrades_query.single();
+	let walls = walls_query.single();
+	let research = research_query.single();
+
+	for mut text in credits_text_query.iter_mut() {
+		text.sections[0].value = locale.format("shop.credits", &[("credits", &format!("{:.0}", upgrades.credits))]);
+	}
+
+	for (upgrade_text, mut text) in upgrade_text_query.iter_mut() {
+		let upgrade = &UPGRADES[upgrade_text.0];
+		let level = upgrades.levels[upgrade_text.0];
+		let name = locale.get(upgrade.name);
+		let cost = format!("{:.0}", upgrades.next_cost(upgrade_text.0));
+		let args: [(&str, &dyn Display); 4] = [("name", &name), ("level", &level), ("max", &upgrade.max_level), ("cost", &cost)];
+		text.sections[0].value = match upgrade.kind {
+			kind if !research.is_unlocked(ResearchEffect::Upgrade(kind)) => locale.format("shop.needs_research", &args),
+			UpgradeKind::Walls(material) if walls.material == material => locale.format("shop.installed", &args),
+			UpgradeKind::Walls(_) if level >= upgrade.max_level => locale.format("shop.install", &args),
+			_ if level >= upgrade.max_level => locale.format("shop.max", &args),
+			_ => locale.format("shop.buy", &args),
+		};
+	}
+}
+
+fn button_upgrade (
+	mut interaction_query: Query<(&UpgradeButton, &Children, &Interaction), Changed<Interaction>>,
+	mut image_query: Query<&mut UiImage>,
+	ui_assets: Res<UiAssets>,
+	mut upgrades_query: Query<&mut Upgrades>,
+	mut walls_query: Query<&mut ReactorWalls>,
+	research_query: Query<&Research>,
+) {
+	let mut upgrades = upgrades_query.single_mut();
+	let mut walls = walls_query.single_mut();
+	let research = research_query.single();
+
+	for (button, children, interaction) in interaction_query.iter_mut() {
+		let child = children.iter().next().unwrap();
+		let mut image = image_query.get_mut(*child).unwrap();
+
+		match interaction {
+			Interaction::Clicked => {
+				let upgrade = &UPGRADES[button.0];
+				if !research.is_unlocked(ResearchEffect::Upgrade(upgrade.kind)) {
+					continue;
+				}
+				let cost = upgrades.next_cost(button.0);
+				if upgrades.levels[button.0] < upgrade.max_level && upgrades.credits >= cost {
+					upgrades.credits -= cost;
+					upgrades.levels[button.0] += 1;
+				}
+				// Owned wall materials can be swapped between freely
+				if let UpgradeKind::Walls(material) = upgrade.kind {
+					if upgrades.levels[button.0] >= upgrade.max_level {
+						walls.material = material;
+					}
+				}
+			}
+			Interaction::Hovered => {
+				image.0 = ui_assets.button_hovered.clone();
+			}
+			Interaction::None => {
+				image.0 = ui_assets.button_idle.clone();
+			}
+		}
+	}
+}
+
+fn button_exit_shop (
+	mut interaction_query: Query<(&ShopExitButton, &Children, &Interaction), Changed<Interaction>>,
+	mut image_query: Query<&mut UiImage>,
+	ui_assets: Res<UiAssets>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, children, interaction) in interaction_query.iter_mut() {
+		let child = children.iter().next().unwrap();
+		let mut image = image_query.get_mut(*child).unwrap();
+
+		match interaction {
+			Interaction::Clicked => {
+				state.pop().expect("Failed to change states");
+			}
+			Interaction::Hovered => {
+				image.0 = ui_assets.button_hovered.clone();
+			}
+			Interaction::None => {
+				image.0 = ui_assets.button_idle.clone();
+			}
+		}
+	}
+}
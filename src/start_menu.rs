@@ -1,6 +1,6 @@
 use bevy::{prelude::*, ui::FocusPolicy, app::AppExit};
 
-use crate::{{GameState, INVISIBLE}, audio::Volume, reactor::generate_unlock_list};
+use crate::{{GameState, INVISIBLE}, audio::Volume, reactor::generate_unlock_list, save::{LoadRequest, SaveSlot, CrashRecovery, read_save}, profiles::ActiveProfile, locale::Locale};
 
 // ---------- PLUGINS ----------
 pub struct StartMenuPlugin;
@@ -17,6 +17,10 @@ impl Plugin for StartMenuPlugin {
 		.add_system_set(SystemSet::on_update(GameState::StartMenu)
 			.with_system(show_buttons)
 			.with_system(button_start)
+			.with_system(button_restore)
+			.with_system(button_continue)
+			.with_system(button_load)
+			.with_system(button_profiles)
 			.with_system(button_settings)
 			.with_system(button_quit)
 		)
@@ -36,6 +40,10 @@ impl Plugin for StartMenuPlugin {
 		.add_system_set(SystemSet::on_update(GameState::SettingsMenu)
 			.with_system(show_buttons)
             .with_system(button_audio_settings)
+			.with_system(button_controls_settings)
+			.with_system(button_display_settings)
+			.with_system(button_language_settings)
+			.with_system(button_accessibility_settings)
 			.with_system(button_return_to_start_menu)
 		)
 		.add_system_set(SystemSet::on_exit(GameState::SettingsMenu)
@@ -62,6 +70,12 @@ impl Plugin for StartMenuPlugin {
 }
 
 // ---------- RESOURCES ----------
+// Backing of option buttons in the settings menus, the one in use is lit up rather than coloured
+pub const OPTION_COLOR: Color = Color::rgba(0.4, 0.9, 0.4, 0.6);
+pub const SELECTED_OPTION_COLOR: Color = Color::rgba(0.95, 0.95, 0.95, 0.9);
+// Only for options that clash with another, like a key bound twice
+pub const CONFLICT_OPTION_COLOR: Color = Color::rgba(0.9, 0.3, 0.3, 0.6);
+
 pub struct UiAssets {
 	pub font: Handle<Font>,
 	pub text_color: Color,
@@ -83,17 +97,41 @@ struct SettingsAudioMenuUi;
 
 // Tags for button logic
 #[derive(Component)]
-struct UiButton;
+pub struct UiButton;
 
 #[derive(Component)]
 struct StartButton;
 
+#[derive(Component)]
+struct RestoreButton;
+
+#[derive(Component)]
+struct ContinueButton;
+
+#[derive(Component)]
+struct LoadButton;
+
+#[derive(Component)]
+struct ProfilesButton;
+
 #[derive(Component)]
 struct SettingsButton;
 
 #[derive(Component)]
 struct SettingsAudioButton;
 
+#[derive(Component)]
+struct SettingsControlsButton;
+
+#[derive(Component)]
+struct SettingsDisplayButton;
+
+#[derive(Component)]
+struct SettingsLanguageButton;
+
+#[derive(Component)]
+struct SettingsAccessibilityButton;
+
 #[derive(Component)]
 struct VolumeControlButton;
 
@@ -116,8 +154,16 @@ struct SelectedOption;
 #[derive(Component)]
 enum MenuButtonAction {
 	Start,
+	Restore,
+	Continue,
+	Load,
+	Profiles,
 	Settings,
 	SettingsAudio,
+	SettingsControls,
+	SettingsDisplay,
+	SettingsLanguage,
+	SettingsAccessibility,
 	ReturnToSettings,
 	ReturnToStartMenu,
 	Quit,
@@ -145,6 +191,9 @@ fn load_menu_assets(
 fn load_menu(
 	mut commands: Commands,
 	ui_assets: Res<UiAssets>,
+	crash_recovery: Res<CrashRecovery>,
+	profile: Res<ActiveProfile>,
+	locale: Res<Locale>,
 ) {
 	let button_style = Style {
 		size: Size::new(Val::Px(250.0), Val::Px(65.0)),
@@ -160,6 +209,10 @@ fn load_menu(
 		color: ui_assets.text_color,
 	};
 
+	// Continue needs a day in progress, Load just needs a save
+	let save = read_save(&profile, SaveSlot::Manual);
+	let has_day = save.as_ref().map_or(false, |save| save.day.is_some());
+
 	commands
 			// Node Bundle to hold the Start Menu UI elements
 			.spawn_bundle(NodeBundle {
@@ -192,6 +245,12 @@ fn load_menu(
 					}),
 				);
 
+				// Active Profile
+				parent.spawn_bundle(TextBundle::from_section(
+					locale.format("menu.playing_as", &[("name", &profile.name)]),
+					button_text_style.clone(),
+				));
+
 				// Lab Button
 				parent
 					.spawn_bundle(ButtonBundle {
@@ -221,7 +280,153 @@ fn load_menu(
 
 							// Lab Button Text
 							parent.spawn_bundle(TextBundle::from_section(
-								"Enter the Lab",
+								locale.get("menu.enter_lab"),
+								button_text_style.clone(),
+							));
+						});
+					});
+
+				// Restore Button, offered after the last session crashed
+				if crash_recovery.available {
+					parent
+						.spawn_bundle(ButtonBundle {
+							style: button_style.clone(),
+							color: ui_assets.button_color.into(),
+							..default()
+						})
+						.insert(MenuButtonAction::Restore)
+						.insert(RestoreButton)
+						.insert(UiButton)
+						.with_children(|parent| {
+
+							// Restore Button Sprite
+							parent
+								.spawn_bundle( ImageBundle {
+									style: Style {
+										size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+										justify_content: JustifyContent::Center,
+										align_items: AlignItems::Center,
+										..Default::default()
+									},
+								image: ui_assets.button_idle.clone().into(),
+								..Default::default()
+							})
+							.insert(FocusPolicy::Pass)
+							.with_children(|parent| {
+
+								// Restore Button Text
+								parent.spawn_bundle(TextBundle::from_section(
+									locale.get("menu.restore"),
+									button_text_style.clone(),
+								));
+							});
+						});
+				}
+
+				// Continue Button
+				if has_day {
+					parent
+						.spawn_bundle(ButtonBundle {
+							style: button_style.clone(),
+							color: ui_assets.button_color.into(),
+							..default()
+						})
+						.insert(MenuButtonAction::Continue)
+						.insert(ContinueButton)
+						.insert(UiButton)
+						.with_children(|parent| {
+
+							// Continue Button Sprite
+							parent
+								.spawn_bundle( ImageBundle {
+									style: Style {
+										size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+										justify_content: JustifyContent::Center,
+										align_items: AlignItems::Center,
+										..Default::default()
+									},
+								image: ui_assets.button_idle.clone().into(),
+								..Default::default()
+							})
+							.insert(FocusPolicy::Pass)
+							.with_children(|parent| {
+
+								// Continue Button Text
+								parent.spawn_bundle(TextBundle::from_section(
+									locale.get("menu.continue"),
+									button_text_style.clone(),
+								));
+							});
+						});
+				}
+
+				// Load Button
+				if save.is_some() {
+					parent
+						.spawn_bundle(ButtonBundle {
+							style: button_style.clone(),
+							color: ui_assets.button_color.into(),
+							..default()
+						})
+						.insert(MenuButtonAction::Load)
+						.insert(LoadButton)
+						.insert(UiButton)
+						.with_children(|parent| {
+
+							// Load Button Sprite
+							parent
+								.spawn_bundle( ImageBundle {
+									style: Style {
+										size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+										justify_content: JustifyContent::Center,
+										align_items: AlignItems::Center,
+										..Default::default()
+									},
+								image: ui_assets.button_idle.clone().into(),
+								..Default::default()
+							})
+							.insert(FocusPolicy::Pass)
+							.with_children(|parent| {
+
+								// Load Button Text
+								parent.spawn_bundle(TextBundle::from_section(
+									locale.get("menu.load"),
+									button_text_style.clone(),
+								));
+							});
+						});
+				}
+
+				// Profiles Button
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: button_style.clone(),
+						color: ui_assets.button_color.into(),
+						..default()
+					})
+					.insert(MenuButtonAction::Profiles)
+					.insert(ProfilesButton)
+					.insert(UiButton)
+					.with_children(|parent| {
+
+						// Profiles Button Sprite
+						parent
+							.spawn_bundle( ImageBundle {
+								style: Style {
+									size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+									justify_content: JustifyContent::Center,
+									align_items: AlignItems::Center,
+									..Default::default()
+								},
+							image: ui_assets.button_idle.clone().into(),
+							..Default::default()
+						})
+						.insert(FocusPolicy::Pass)
+						.with_children(|parent| {
+
+							// Profiles Button Text
+							parent.spawn_bundle(TextBundle::from_section(
+								locale.get("menu.profiles"),
 								button_text_style.clone(),
 							));
 						});
@@ -256,7 +461,7 @@ fn load_menu(
 
 							// Settings Button Text
 							parent.spawn_bundle(TextBundle::from_section(
-								"Settings",
+								locale.get("menu.settings"),
 								button_text_style.clone(),
 							));
 						});
@@ -291,7 +496,7 @@ fn load_menu(
 
 							// Quit Button Text
 							parent.spawn_bundle(TextBundle::from_section(
-								"Quit",
+								locale.get("menu.quit"),
 								button_text_style.clone(),
 							));
 						});
@@ -302,6 +507,7 @@ fn load_menu(
 fn load_settings (
 	mut commands: Commands,
 	ui_assets: Res<UiAssets>,
+	locale: Res<Locale>,
 ) {
 	let button_style = Style {
 		size: Size::new(Val::Px(250.0), Val::Px(65.0)),
@@ -361,7 +567,143 @@ fn load_settings (
 						.with_children(|parent| {
 							parent
 								.spawn_bundle(TextBundle::from_section(
-									"Audio",
+									locale.get("settings.audio"),
+									button_text_style.clone(),
+								));
+						});
+					});
+
+				// Display Settings Button
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: button_style.clone(),
+						color: ui_assets.button_color.into(),
+						..default()
+					})
+					.insert(MenuButtonAction::SettingsDisplay)
+					.insert(SettingsDisplayButton)
+					.insert(UiButton)
+					.with_children(|parent| {
+
+						// Display Settings Button Sprite
+						parent
+							.spawn_bundle(ImageBundle {
+								style: Style {
+									size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+									justify_content: JustifyContent::Center,
+									align_items: AlignItems::Center,
+									..Default::default()
+								},
+							image: ui_assets.button_idle.clone().into(),
+							..Default::default()
+						})
+						.insert(FocusPolicy::Pass)
+						.with_children(|parent| {
+							parent
+								.spawn_bundle(TextBundle::from_section(
+									locale.get("settings.display"),
+									button_text_style.clone(),
+								));
+						});
+					});
+
+				// Language Settings Button
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: button_style.clone(),
+						color: ui_assets.button_color.into(),
+						..default()
+					})
+					.insert(MenuButtonAction::SettingsLanguage)
+					.insert(SettingsLanguageButton)
+					.insert(UiButton)
+					.with_children(|parent| {
+
+						// Language Settings Button Sprite
+						parent
+							.spawn_bundle(ImageBundle {
+								style: Style {
+									size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+									justify_content: JustifyContent::Center,
+									align_items: AlignItems::Center,
+									..Default::default()
+								},
+							image: ui_assets.button_idle.clone().into(),
+							..Default::default()
+						})
+						.insert(FocusPolicy::Pass)
+						.with_children(|parent| {
+							parent
+								.spawn_bundle(TextBundle::from_section(
+									locale.get("settings.language"),
+									button_text_style.clone(),
+								));
+						});
+					});
+
+				// Accessibility Settings Button
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: button_style.clone(),
+						color: ui_assets.button_color.into(),
+						..default()
+					})
+					.insert(MenuButtonAction::SettingsAccessibility)
+					.insert(SettingsAccessibilityButton)
+					.insert(UiButton)
+					.with_children(|parent| {
+
+						// Accessibility Settings Button Sprite
+						parent
+							.spawn_bundle(ImageBundle {
+								style: Style {
+									size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+									justify_content: JustifyContent::Center,
+									align_items: AlignItems::Center,
+									..Default::default()
+								},
+							image: ui_assets.button_idle.clone().into(),
+							..Default::default()
+						})
+						.insert(FocusPolicy::Pass)
+						.with_children(|parent| {
+							parent
+								.spawn_bundle(TextBundle::from_section(
+									locale.get("settings.accessibility"),
+									button_text_style.clone(),
+								));
+						});
+					});
+
+				// Controls Settings Button
+				parent
+					.spawn_bundle(ButtonBundle {
+						style: button_style.clone(),
+						color: ui_assets.button_color.into(),
+						..default()
+					})
+					.insert(MenuButtonAction::SettingsControls)
+					.insert(SettingsControlsButton)
+					.insert(UiButton)
+					.with_children(|parent| {
+
+						// Controls Settings Button Sprite
+						parent
+							.spawn_bundle(ImageBundle {
+								style: Style {
+									size: Size::new(Val::Percent(100.0) , Val::Percent(100.0)),
+									justify_content: JustifyContent::Center,
+									align_items: AlignItems::Center,
+									..Default::default()
+								},
+							image: ui_assets.button_idle.clone().into(),
+							..Default::default()
+						})
+						.insert(FocusPolicy::Pass)
+						.with_children(|parent| {
+							parent
+								.spawn_bundle(TextBundle::from_section(
+									locale.get("settings.controls"),
 									button_text_style.clone(),
 								));
 						});
@@ -399,7 +741,7 @@ fn load_settings (
 					.with_children(|parent| {
 						parent
 							.spawn_bundle(TextBundle::from_section(
-								"Back",
+								locale.get("menu.back"),
 								button_text_style.clone(),
 							));
 					});
@@ -411,6 +753,7 @@ fn load_settings_audio (
     mut commands: Commands,
     volume: Res<Volume>,
     ui_assets: Res<UiAssets>,
+    locale: Res<Locale>,
 ) {
     let button_style = Style {
 		size: Size::new(Val::Px(250.0), Val::Px(65.0)),
@@ -450,7 +793,7 @@ fn load_settings_audio (
                     })
                     .with_children(|parent| {
                         parent.spawn_bundle(TextBundle::from_section(
-                            "Volume",
+                            locale.get("settings.volume"),
                             button_text_style.clone(),
                         ));
                         for volume_setting in [0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9] {
@@ -501,7 +844,7 @@ fn load_settings_audio (
                             .with_children(|parent| {
                                 parent
                                     .spawn_bundle(TextBundle::from_section(
-                                        "Back",
+                                        locale.get("menu.back"),
                                         button_text_style.clone(),
                                     ));
                             });
@@ -510,7 +853,7 @@ fn load_settings_audio (
 }
 
 // BUTTON SYSTEMS
-fn show_buttons (
+pub fn show_buttons (
 	mut interaction_query: Query<(&UiButton, &Children, &Interaction), Changed<Interaction>>,
 	mut image_query: Query<&mut UiImage>,
 	ui_assets: Res<UiAssets>,
@@ -531,13 +874,16 @@ fn show_buttons (
 	}
 }
 
+// Starting, continuing or loading anything else passes on the crash autosave
 fn button_start (
+	mut crash_recovery: ResMut<CrashRecovery>,
 	mut interaction_query: Query<(&StartButton, &Interaction), Changed<Interaction>>,
 	mut state: ResMut<State<GameState>>,
 ) {
 	for (_, interaction) in interaction_query.iter_mut() {
 		match interaction {
 			Interaction::Clicked => {
+				crash_recovery.available = false;
 				state.push(GameState::Lab).expect("Failed to change states");
 			}
 			Interaction::Hovered | Interaction::None => {
@@ -546,6 +892,87 @@ fn button_start (
 	}
 }
 
+// Picks up from the last autosave of a session that crashed
+fn button_restore (
+	mut load_request: ResMut<LoadRequest>,
+	mut crash_recovery: ResMut<CrashRecovery>,
+	mut interaction_query: Query<(&RestoreButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, interaction) in interaction_query.iter_mut() {
+		match interaction {
+			Interaction::Clicked => {
+				load_request.pending = true;
+				load_request.resume = true;
+				load_request.slot = SaveSlot::Autosave;
+				crash_recovery.available = false;
+				state.push(GameState::Lab).expect("Failed to change states");
+			}
+			Interaction::Hovered | Interaction::None => {
+			}
+		}
+	}
+}
+
+// Picks the saved day back up where it was left
+fn button_continue (
+	mut load_request: ResMut<LoadRequest>,
+	mut crash_recovery: ResMut<CrashRecovery>,
+	mut interaction_query: Query<(&ContinueButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, interaction) in interaction_query.iter_mut() {
+		match interaction {
+			Interaction::Clicked => {
+				load_request.pending = true;
+				load_request.resume = true;
+				load_request.slot = SaveSlot::Manual;
+				crash_recovery.available = false;
+				state.push(GameState::Lab).expect("Failed to change states");
+			}
+			Interaction::Hovered | Interaction::None => {
+			}
+		}
+	}
+}
+
+// Restores the saved progression and starts from the Lab
+fn button_load (
+	mut load_request: ResMut<LoadRequest>,
+	mut crash_recovery: ResMut<CrashRecovery>,
+	mut interaction_query: Query<(&LoadButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, interaction) in interaction_query.iter_mut() {
+		match interaction {
+			Interaction::Clicked => {
+				load_request.pending = true;
+				load_request.resume = false;
+				load_request.slot = SaveSlot::Manual;
+				crash_recovery.available = false;
+				state.push(GameState::Lab).expect("Failed to change states");
+			}
+			Interaction::Hovered | Interaction::None => {
+			}
+		}
+	}
+}
+
+fn button_profiles (
+	mut interaction_query: Query<(&ProfilesButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+	for (_, interaction) in interaction_query.iter_mut() {
+		match interaction {
+			Interaction::Clicked => {
+				state.push(GameState::ProfileMenu).expect("Failed to change states");
+			}
+			Interaction::Hovered | Interaction::None => {
+			}
+		}
+	}
+}
+
 fn button_settings (
 	mut interaction_query: Query<(&SettingsButton, &Interaction), Changed<Interaction>>,
 	mut state: ResMut<State<GameState>>,
@@ -576,6 +1003,66 @@ fn button_audio_settings (
 	}
 }
 
+fn button_controls_settings (
+    mut interaction_query: Query<(&SettingsControlsButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+    for (_, interaction) in interaction_query.iter_mut() {
+		match interaction {
+			Interaction::Clicked => {
+				state.push(GameState::SettingsControlsMenu).expect("Failed to change states");
+			}
+			Interaction::Hovered | Interaction::None => {
+			}
+		}
+	}
+}
+
+fn button_display_settings (
+    mut interaction_query: Query<(&SettingsDisplayButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+    for (_, interaction) in interaction_query.iter_mut() {
+		match interaction {
+			Interaction::Clicked => {
+				state.push(GameState::SettingsDisplayMenu).expect("Failed to change states");
+			}
+			Interaction::Hovered | Interaction::None => {
+			}
+		}
+	}
+}
+
+fn button_language_settings (
+    mut interaction_query: Query<(&SettingsLanguageButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+    for (_, interaction) in interaction_query.iter_mut() {
+		match interaction {
+			Interaction::Clicked => {
+				state.push(GameState::SettingsLanguageMenu).expect("Failed to change states");
+			}
+			Interaction::Hovered | Interaction::None => {
+			}
+		}
+	}
+}
+
+fn button_accessibility_settings (
+    mut interaction_query: Query<(&SettingsAccessibilityButton, &Interaction), Changed<Interaction>>,
+	mut state: ResMut<State<GameState>>,
+) {
+    for (_, interaction) in interaction_query.iter_mut() {
+		match interaction {
+			Interaction::Clicked => {
+				state.push(GameState::SettingsAccessibilityMenu).expect("Failed to change states");
+			}
+			Interaction::Hovered | Interaction::None => {
+			}
+		}
+	}
+}
+
 // AUDIO SETTING SYSTEMS
 fn button_system(
 	mut interaction_query: Query<